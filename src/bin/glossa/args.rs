@@ -0,0 +1,139 @@
+use std::path::PathBuf;
+
+pub(crate) const USAGE: &str = "\
+Usage:
+  glossa check <FILE|DIR>...
+  glossa lint <FILE|DIR>...
+  glossa render <FILE> <KEY> [--param <NAME>=<VALUE>]...
+  glossa compile <FILE|DIR> [-o <OUTPUT>]
+  glossa decompile <FILE>
+
+Commands:
+  check      Parses the files, and checks references and cycles.
+             For a <DIR>, the files of each locale are checked together.
+  lint       Same as check, and also checks that TOML files are formatted
+  render     Renders the message <KEY> with the given parameters
+  compile    Checks the file, and writes it as a bincode bundle.
+             <OUTPUT> defaults to the input file with the `bincode` extension.
+             For a <DIR>, each locale (a sub-directory, or a file directly in
+             it) is compiled into one bundle of the <OUTPUT> dir, e.g.,
+             `en/*.toml` => `en.bincode`. Nothing is written if any locale
+             has an error.
+  decompile  Prints a bincode bundle as TOML source
+
+Supported files: *.toml, *.json";
+
+#[derive(Debug)]
+pub(crate) enum Command {
+  Help,
+  Check(Vec<PathBuf>),
+  Lint(Vec<PathBuf>),
+  Render {
+    file: PathBuf,
+    key: String,
+    params: Vec<(String, String)>,
+  },
+  Compile {
+    input: PathBuf,
+    output: Option<PathBuf>,
+  },
+  Decompile(PathBuf),
+}
+
+impl Command {
+  pub(crate) fn parse<I: Iterator<Item = String>>(
+    mut args: I,
+  ) -> Result<Self, String> {
+    let cmd = match args.next() {
+      Some(cmd) => cmd,
+      _ => return Ok(Self::Help),
+    };
+
+    let files = |args: I| {
+      let files = args
+        .map(PathBuf::from)
+        .collect::<Vec<_>>();
+      match files.is_empty() {
+        true => Err(format!("`{cmd}` requires at least one <FILE>")),
+        _ => Ok(files),
+      }
+    };
+
+    match cmd.as_str() {
+      "help" | "-h" | "--help" => Ok(Self::Help),
+      "check" => files(args).map(Self::Check),
+      "lint" => files(args).map(Self::Lint),
+      "render" => Self::parse_render(args),
+      "compile" => Self::parse_compile(args),
+      "decompile" => match (args.next(), args.next()) {
+        (Some(file), None) => Ok(Self::Decompile(file.into())),
+        _ => Err("`decompile` requires exactly one <FILE>".into()),
+      },
+      _ => Err(format!("unknown command `{cmd}`")),
+    }
+  }
+
+  fn parse_render<I: Iterator<Item = String>>(mut args: I) -> Result<Self, String> {
+    let mut positional = Vec::with_capacity(2);
+    let mut params = Vec::new();
+
+    while let Some(arg) = args.next() {
+      let param = match arg.strip_prefix("--param") {
+        Some("") => args
+          .next()
+          .ok_or("`--param` requires <NAME>=<VALUE>")?,
+        Some(p) => match p.strip_prefix('=') {
+          Some(p) => p.to_owned(),
+          _ => return Err(format!("unknown option `{arg}`")),
+        },
+        _ if arg.starts_with("--") => return Err(format!("unknown option `{arg}`")),
+        _ => {
+          positional.push(arg);
+          continue;
+        }
+      };
+
+      match param.split_once('=') {
+        Some((name, value)) => params.push((name.to_owned(), value.to_owned())),
+        _ => {
+          return Err(format!(
+            "invalid parameter `{param}`, expected <NAME>=<VALUE>"
+          ));
+        }
+      }
+    }
+
+    match <[String; 2]>::try_from(positional) {
+      Ok([file, key]) => Ok(Self::Render {
+        file: file.into(),
+        key,
+        params,
+      }),
+      _ => Err("`render` requires <FILE> and <KEY>".into()),
+    }
+  }
+
+  fn parse_compile<I: Iterator<Item = String>>(mut args: I) -> Result<Self, String> {
+    let mut input = None;
+    let mut output = None;
+
+    while let Some(arg) = args.next() {
+      match arg.as_str() {
+        "-o" | "--output" => {
+          let path = args
+            .next()
+            .ok_or(format!("`{arg}` requires <OUTPUT>"))?;
+          output = Some(path.into())
+        }
+        _ if arg.starts_with('-') => return Err(format!("unknown option `{arg}`")),
+        _ if input.is_none() => input = Some(arg.into()),
+        _ => return Err(format!("unexpected argument `{arg}`")),
+      }
+    }
+
+    match input {
+      Some(input) => Ok(Self::Compile { input, output }),
+      _ => Err("`compile` requires <FILE> or <DIR>".into()),
+    }
+  }
+}
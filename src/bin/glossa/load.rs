@@ -0,0 +1,31 @@
+use glossa_dsl::{
+  Error, Resolver,
+  resolver::{AST, BTreeRawMap},
+};
+use tap::Pipe;
+
+/// Parses every message, and collects all parse errors instead of
+/// stopping at the first one.
+///
+/// The resolver only contains the messages that were parsed successfully.
+/// Each error comes with the key of its message.
+pub(crate) fn parse_messages(raw: &BTreeRawMap) -> (Resolver, Vec<(&str, Error)>) {
+  let mut errors = Vec::new();
+
+  let resolver = raw
+    .iter()
+    .filter_map(|(key, value)| {
+      match Resolver::try_from_str_entries(core::iter::once((key, value))) {
+        Ok(res) => Some(res.into_inner()),
+        Err(e) => {
+          errors.push((key.as_str(), e));
+          None
+        }
+      }
+    })
+    .flatten()
+    .collect::<AST>()
+    .pipe(Resolver::from);
+
+  (resolver, errors)
+}
@@ -0,0 +1,328 @@
+//! Command-line tool for translation files
+//!
+//! ```ignore,sh
+//! cargo run --package glossa-dsl --features=cli -- check locales/en.toml
+//! ```
+mod args;
+mod load;
+
+use std::{
+  fs,
+  path::{Path, PathBuf},
+  process::ExitCode,
+};
+
+use args::{Command, USAGE};
+use glossa_dsl::{
+  Resolver,
+  formatter::{is_toml_formatted, resolver_to_toml},
+  loader::{Locales, list_translation_files, load_locales_dir, read_raw_file},
+};
+
+fn main() -> ExitCode {
+  let cmd = match Command::parse(std::env::args().skip(1)) {
+    Ok(cmd) => cmd,
+    Err(e) => {
+      eprintln!("error: {e}\n\n{USAGE}");
+      return ExitCode::from(2);
+    }
+  };
+
+  let ok = match cmd {
+    Command::Help => {
+      println!("{USAGE}");
+      true
+    }
+    Command::Check(files) => check_files(&files, false),
+    Command::Lint(files) => check_files(&files, true),
+    Command::Render { file, key, params } => render(&file, &key, &params),
+    Command::Compile { input, output } => compile(&input, output.as_deref()),
+    Command::Decompile(file) => decompile(&file),
+  };
+
+  match ok {
+    true => ExitCode::SUCCESS,
+    _ => ExitCode::FAILURE,
+  }
+}
+
+fn report(path: &Path, msg: impl core::fmt::Display) {
+  eprintln!("error: {}: {msg}", path.display())
+}
+
+/// Reports every problem of the files (or locale directories), and returns
+/// `true` if there is none.
+fn check_files<P: AsRef<Path>>(paths: &[P], lint: bool) -> bool {
+  let (files, errors) = paths
+    .iter()
+    .map(AsRef::as_ref)
+    .map(|path| match path.is_dir() {
+      true => check_dir(path, lint),
+      _ => (1, check_file(path, lint)),
+    })
+    .fold((0, 0), |(files, errors), (f, e)| (files + f, errors + e));
+
+  match errors {
+    0 => {
+      eprintln!("{files} file(s) checked, no errors");
+      true
+    }
+    n => {
+      eprintln!("{files} file(s) checked, {n} error(s)");
+      false
+    }
+  }
+}
+
+/// Returns the number of problems found.
+fn check_file(path: &Path, lint: bool) -> usize {
+  let errors = load_checked(path)
+    .err()
+    .unwrap_or_default();
+
+  match lint {
+    true => errors + check_format(path),
+    _ => errors,
+  }
+}
+
+/// Checks every locale of a directory as a whole (see [load_locales_dir]),
+/// so that a file may reference messages of other files of its locale.
+///
+/// Returns the number of files, and the number of problems found.
+fn check_dir(dir: &Path, lint: bool) -> (usize, usize) {
+  let files = match list_translation_files(dir) {
+    Ok(files) => files,
+    Err(e) => {
+      eprintln!("error: {e}");
+      return (0, 1);
+    }
+  };
+
+  let errors = load_checked_locales(dir)
+    .err()
+    .unwrap_or_default();
+  let unformatted = match lint {
+    true => files
+      .iter()
+      .map(|file| check_format(file))
+      .sum(),
+    _ => 0,
+  };
+
+  (files.len(), errors + unformatted)
+}
+
+/// Checks that a TOML file is formatted in canonical style. Other files are
+/// not checked.
+///
+/// Returns the number of problems found.
+fn check_format(path: &Path) -> usize {
+  if path.extension() != Some("toml".as_ref()) {
+    return 0;
+  }
+
+  fs::read_to_string(path)
+    .map_err(|e| e.to_string())
+    .and_then(|src| is_toml_formatted(&src).map_err(|e| e.to_string()))
+    .map_or_else(
+      |e| {
+        report(path, e);
+        1
+      },
+      |formatted| {
+        if !formatted {
+          report(path, "not formatted in canonical style");
+        }
+        usize::from(!formatted)
+      },
+    )
+}
+
+/// Loads a translation file, and runs parse, reference and cycle checks.
+///
+/// On failure, every problem is reported, and the number of problems is
+/// returned.
+fn load_checked(path: &Path) -> Result<Resolver, usize> {
+  let raw = read_raw_file(path).map_err(|e| {
+    // The error already contains the path.
+    eprintln!("error: {e}");
+    1_usize
+  })?;
+
+  let (resolver, parse_errors) = load::parse_messages(&raw);
+  let errors = parse_errors
+    .into_iter()
+    .map(|(_, e)| e)
+    .chain(resolver.validation_errors())
+    .inspect(|e| report(path, e))
+    .count();
+
+  match errors {
+    0 => Ok(resolver),
+    n => Err(n),
+  }
+}
+
+/// Loads every locale of a directory, and checks references and cycles of
+/// each locale as a whole.
+///
+/// On failure, every problem is reported, and the number of problems is
+/// returned.
+fn load_checked_locales(dir: &Path) -> Result<Locales, usize> {
+  let locales = load_locales_dir(dir).map_err(|e| {
+    // The error already contains the path.
+    eprintln!("error: {e}");
+    1_usize
+  })?;
+
+  let errors = locales
+    .iter()
+    .flat_map(|(lang, resolver)| {
+      let path = dir.join(lang.as_str());
+      resolver
+        .validation_errors()
+        .into_iter()
+        .map(move |e| (path.clone(), e))
+    })
+    .inspect(|(path, e)| report(path, e))
+    .count();
+
+  match errors {
+    0 => Ok(locales),
+    n => Err(n),
+  }
+}
+
+/// Similar to [load_checked], but only checks `key` and the messages it
+/// references, so that an unrelated broken message does not block it.
+fn load_for_key(path: &Path, key: &str) -> Result<Resolver, usize> {
+  let raw = read_raw_file(path).map_err(|e| {
+    eprintln!("error: {e}");
+    1_usize
+  })?;
+
+  let (resolver, parse_errors) = load::parse_messages(&raw);
+  let graph = resolver.dependency_graph();
+  // A message that failed to parse is still reachable as an undefined
+  // reference.
+  let needed = graph.reachable_from([key]);
+
+  let errors = parse_errors
+    .into_iter()
+    .filter(|(k, _)| *k == key || needed.contains(k))
+    .map(|(_, e)| e)
+    .chain(
+      resolver
+        .contains_key(key)
+        .then(|| resolver.validate_key(key).err())
+        .flatten(),
+    )
+    .inspect(|e| report(path, e))
+    .count();
+
+  match errors {
+    0 => Ok(resolver),
+    n => Err(n),
+  }
+}
+
+fn render(path: &Path, key: &str, params: &[(String, String)]) -> bool {
+  let Ok(resolver) = load_for_key(path, key) else {
+    return false;
+  };
+
+  let ctx = params
+    .iter()
+    .map(|(k, v)| (k.as_str(), v.as_str()))
+    .collect::<Vec<_>>();
+
+  match resolver.get_with_context(key, &ctx) {
+    Ok(text) => {
+      println!("{text}");
+      true
+    }
+    Err(e) => {
+      report(path, e);
+      false
+    }
+  }
+}
+
+/// Compiles a file, or every locale of a directory.
+fn compile(input: &Path, output: Option<&Path>) -> bool {
+  if !input.is_dir() {
+    let output = output
+      .map(PathBuf::from)
+      .unwrap_or_else(|| input.with_extension("bincode"));
+    return compile_file(input, &output);
+  }
+
+  let Some(out_dir) = output else {
+    report(input, "compiling a directory requires `-o <OUTPUT>`");
+    return false;
+  };
+
+  // Nothing is written unless every locale is valid.
+  let Ok(locales) = load_checked_locales(input) else {
+    return false;
+  };
+  if let Err(e) = fs::create_dir_all(out_dir) {
+    report(out_dir, e);
+    return false;
+  }
+
+  // One bundle per locale, e.g., `locales/en/*.toml` => `out/en.bincode`.
+  locales
+    .iter()
+    .map(|(lang, resolver)| {
+      let output = out_dir.join(format!("{lang}.bincode"));
+      match resolver.encode_bin(&output) {
+        Ok(_) => {
+          eprintln!(
+            "{} -> {}",
+            input
+              .join(lang.as_str())
+              .display(),
+            output.display()
+          );
+          true
+        }
+        Err(e) => {
+          report(&output, e);
+          false
+        }
+      }
+    })
+    .fold(true, |all_ok, ok| all_ok & ok)
+}
+
+fn compile_file(input: &Path, output: &Path) -> bool {
+  let Ok(resolver) = load_checked(input) else {
+    return false;
+  };
+
+  match resolver.encode_bin(output) {
+    Ok(_) => {
+      eprintln!("{} -> {}", input.display(), output.display());
+      true
+    }
+    Err(e) => {
+      report(output, e);
+      false
+    }
+  }
+}
+
+fn decompile(path: &Path) -> bool {
+  match Resolver::decode_bin(path).and_then(|res| resolver_to_toml(&res)) {
+    Ok(toml) => {
+      print!("{toml}");
+      true
+    }
+    Err(e) => {
+      report(path, e);
+      false
+    }
+  }
+}
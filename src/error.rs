@@ -24,6 +24,20 @@ pub enum ResolverError {
   #[error("Parse error: {0}")]
   ParseError(MiniStr),
   //
+  /// (message key, undefined reference)
+  #[error("Undefined reference in '{0}': {1}")]
+  UndefinedReference(MiniStr, MiniStr),
+  //
+  #[error("Conflicting key: {0}")]
+  ConflictingKey(MiniStr),
+  //
+  /// Reference path, e.g., `a -> b -> a`
+  #[error("Cyclic reference: {0}")]
+  CyclicReference(MiniStr),
+  //
+  #[error("Source error: {0}")]
+  SourceError(MiniStr),
+  //
   #[cfg(feature = "std")]
   #[error("I/O error: {0}")]
   IoError(#[from] std::io::Error),
@@ -42,5 +56,23 @@ pub enum ResolverError {
 
   #[cfg(feature = "toml")]
   #[error("TOML serialization error: {0}")]
-  EncodeTomlError(#[from] toml::ser::Error), //
+  EncodeTomlError(#[from] toml::ser::Error),
+
+  #[cfg(feature = "formatter")]
+  #[error("TOML document error: {0}")]
+  DecodeTomlDocError(#[from] toml_edit::TomlError),
+
+  #[cfg(feature = "loader")]
+  #[error("JSON deserialization error: {0}")]
+  DecodeJsonError(#[from] serde_json::Error),
+
+  /// Error of a translation file
+  #[cfg(feature = "loader")]
+  #[error("{}: {}", .0.display(), .1)]
+  InFile(std::path::PathBuf, alloc::boxed::Box<ResolverError>),
+
+  /// (key, first file, second file)
+  #[cfg(feature = "loader")]
+  #[error("Duplicate key '{}' in {} and {}", .0, .1.display(), .2.display())]
+  DuplicateKey(MiniStr, std::path::PathBuf, std::path::PathBuf), //
 }
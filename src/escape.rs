@@ -0,0 +1,77 @@
+//! Escaping of rendered text
+//!
+//! See [EscapeMode](crate::resolver::EscapeMode).
+use crate::MiniStr;
+
+/// Escapes a string for an output format, e.g., HTML, XML or a shell.
+///
+/// It is implemented for closures, so an ad-hoc escaper does not need a new
+/// type:
+///
+/// ```
+/// use glossa_dsl::{escape::Escaper, resolver::MiniStr};
+///
+/// let markdown = |input: &str, output: &mut MiniStr| {
+///   for c in input.chars() {
+///     if "\\`*_[]<>#".contains(c) {
+///       output.push('\\');
+///     }
+///     output.push(c);
+///   }
+/// };
+///
+/// let mut output = MiniStr::default();
+/// markdown.escape("*bold*", &mut output);
+/// assert_eq!(output, r"\*bold\*");
+/// ```
+pub trait Escaper {
+  /// Appends the escaped `input` to `output`.
+  fn escape(&self, input: &str, output: &mut MiniStr);
+}
+
+impl<F> Escaper for F
+where
+  F: Fn(&str, &mut MiniStr),
+{
+  fn escape(&self, input: &str, output: &mut MiniStr) {
+    self(input, output)
+  }
+}
+
+/// Escapes `&`, `<`, `>`, `"` and `'`, which is safe for HTML text and quoted
+/// attribute values.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct HtmlEscaper;
+
+impl Escaper for HtmlEscaper {
+  fn escape(&self, input: &str, output: &mut MiniStr) {
+    let mut rest = input;
+    while let Some(idx) = rest.find(['&', '<', '>', '"', '\'']) {
+      output.push_str(&rest[..idx]);
+      output.push_str(match rest.as_bytes()[idx] {
+        b'&' => "&amp;",
+        b'<' => "&lt;",
+        b'>' => "&gt;",
+        b'"' => "&quot;",
+        _ => "&#39;",
+      });
+      rest = &rest[idx + 1..];
+    }
+    output.push_str(rest)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_html_escaper() {
+    let mut output = MiniStr::default();
+    HtmlEscaper.escape(r#"<a href="x">Tom & 'Jerry'</a>"#, &mut output);
+    assert_eq!(
+      output,
+      "&lt;a href=&quot;x&quot;&gt;Tom &amp; &#39;Jerry&#39;&lt;/a&gt;"
+    );
+  }
+}
@@ -0,0 +1,204 @@
+//! Source formatter for TOML translation files.
+//!
+//! Every string value is parsed and written back in canonical style (see
+//! [Template::to_source]). Key order, comments and non-string values are
+//! kept as-is.
+//!
+//! ## Example
+//!
+//! ```
+//! use glossa_dsl::formatter::format_toml;
+//!
+//! let src = r#"
+//! ## Greeting of the home page
+//! greeting = "{h}, {$name}!"
+//! h = "Hello" # short
+//!
+//! salutation = """
+//!     $gender ->
+//!       [male] Mr.
+//!     *[female] Ms.
+//! """
+//! "#;
+//!
+//! let formatted = format_toml(src)?;
+//!
+//! assert_eq!(
+//!   formatted,
+//!   r#"
+//! ## Greeting of the home page
+//! greeting = "{ h }, { $name }!"
+//! h = "Hello" # short
+//!
+//! salutation = """
+//! $gender ->
+//!   [male] Mr.
+//!   *[female] Ms.
+//! """
+//! "#
+//! );
+//!
+//! # Ok::<(), glossa_dsl::Error>(())
+//! ```
+use std::{collections::BTreeMap, fs, path::Path};
+
+use compact_str::{ToCompactString, format_compact};
+use tap::Pipe;
+use toml_edit::{DocumentMut, Formatted, Item, TableLike, Value};
+
+use crate::{
+  MiniStr, Resolver,
+  error::{ResolverError, ResolverResult},
+  parsers::parse_value_or_map_err,
+  template::Template,
+};
+
+/// Formats a TOML translation file, and returns the formatted text.
+pub fn format_toml(src: &str) -> ResolverResult<String> {
+  let mut doc = src.parse::<DocumentMut>()?;
+  format_table(doc.as_table_mut(), "")?;
+  doc.to_string().pipe(Ok)
+}
+
+/// Checks whether a TOML translation file is already formatted.
+///
+/// It is useful for CI, like `cargo fmt --check`.
+pub fn is_toml_formatted(src: &str) -> ResolverResult<bool> {
+  format_toml(src).map(|formatted| formatted == src)
+}
+
+/// Formats a TOML translation file in place.
+///
+/// Returns `true` if the file was changed. An unchanged file is not
+/// written.
+pub fn format_toml_file<P: AsRef<Path>>(path: P) -> ResolverResult<bool> {
+  let path = path.as_ref();
+  let src = fs::read_to_string(path)?;
+  let formatted = format_toml(&src)?;
+
+  match formatted == src {
+    true => Ok(false),
+    _ => fs::write(path, formatted)
+      .map(|_| true)?
+      .pipe(Ok),
+  }
+}
+
+/// Formats the value of a single message.
+///
+/// The output of a selector has a trailing newline.
+///
+/// The output is parsed again, and [ResolverError::SourceError] is returned
+/// if it is not the same template, e.g., for a reference whose key looks
+/// like a string literal.
+///
+/// ## Example
+///
+/// ```
+/// use glossa_dsl::formatter::format_value;
+///
+/// assert_eq!(format_value("greeting", "{h}   {$name}")?, "{ h }   { $name }");
+///
+/// # Ok::<(), glossa_dsl::Error>(())
+/// ```
+pub fn format_value<K: core::fmt::Display>(
+  key: K,
+  value: &str,
+) -> ResolverResult<MiniStr> {
+  let template = parse_value_or_map_err(&key, value)?;
+  let formatted = template_to_value(&template)?;
+
+  // A formatter must never change what a message renders.
+  match parse_value_or_map_err(&key, &formatted) {
+    Ok(reparsed) if reparsed == template => Ok(formatted),
+    _ => format_compact!("formatting '{key}' would change the message")
+      .pipe(ResolverError::SourceError)
+      .pipe(Err),
+  }
+}
+
+/// Writes all messages of the resolver as a TOML document, in key order.
+///
+/// It is useful to get human-readable source back from a resolver that was
+/// loaded from bincode or JSON.
+///
+/// ## Example
+///
+/// ```
+/// use glossa_dsl::{Resolver, formatter::resolver_to_toml};
+///
+/// let res: Resolver = [
+///   ("greeting", "{h} {$name}"),
+///   ("h", "$period ->\n [morning] Good Morning\n *[other] Hello"),
+/// ]
+/// .try_into()?;
+///
+/// assert_eq!(
+///   resolver_to_toml(&res)?,
+///   r#"greeting = "{ h } { $name }"
+/// h = """
+/// $period ->
+///   [morning] Good Morning
+///   *[other] Hello
+/// """
+/// "#
+/// );
+///
+/// # Ok::<(), glossa_dsl::Error>(())
+/// ```
+pub fn resolver_to_toml(resolver: &Resolver) -> ResolverResult<String> {
+  resolver
+    .iter()
+    .map(|(key, tmpl)| (key.as_str(), tmpl))
+    .collect::<BTreeMap<_, _>>()
+    .into_iter()
+    .try_fold(DocumentMut::new(), |mut doc, (key, tmpl)| {
+      doc[key] = template_to_value(tmpl)?
+        .as_str()
+        .pipe(toml_edit::value);
+      Ok::<_, ResolverError>(doc)
+    })?
+    .to_string()
+    .pipe(Ok)
+}
+
+/// A selector ends with a newline, so that the closing quotes of a
+/// multi-line TOML string are on their own line.
+fn template_to_value(template: &Template) -> ResolverResult<MiniStr> {
+  let mut src = template.to_source()?;
+
+  if let Template::Conditional(_) = template {
+    src.push('\n')
+  }
+  Ok(src)
+}
+
+fn format_table(table: &mut dyn TableLike, parent: &str) -> ResolverResult<()> {
+  table
+    .iter_mut()
+    .try_for_each(|(key, item)| {
+      let path = match parent {
+        "" => key.get().to_compact_string(),
+        p => format_compact!("{p}.{}", key.get()),
+      };
+
+      match item {
+        Item::Value(Value::String(value)) => format_string(value, &path),
+        _ => match item.as_table_like_mut() {
+          Some(t) => format_table(t, &path),
+          _ => Ok(()),
+        },
+      }
+    })
+}
+
+fn format_string(value: &mut Formatted<String>, key: &str) -> ResolverResult<()> {
+  let formatted = format_value(key, value.value())?;
+
+  if formatted != value.value() {
+    let decor = value.decor().clone();
+    *value = Formatted::new(formatted.into());
+    *value.decor_mut() = decor;
+  }
+  Ok(())
+}
@@ -25,6 +25,16 @@ A **domain-specific language** designed exclusively for localization (L10n).
   - Efficient binary serialization
 - ["toml"]
   - Enables `ResolverError::{DecodeTomlError, EncodeTomlError}`
+- ["formatter"]
+  - Rewrites TOML translation files in canonical style
+- ["loader"]
+  - Loads per-locale directories of TOML/JSON translation files
+- ["reload"]
+  - Reloads translation files on change, for development
+- ["shared"]
+  - Thread-safe `SharedResolver` with lock-free reads and atomic replacement
+- ["cli"]
+  - Builds the `glossa` binary
 
 ## Examples
 
@@ -101,18 +111,130 @@ fn main() -> ResolverResult<()> {
   Ok(())
 }
 ```
+
+#### String Literals
+
+The brace trick above trims whitespace. For exact output, use a string-literal
+placeable: `{"..."}`.
+
+- `{"{"}` => `"{"`
+- `{"}"}` => `"}"`
+- `{"  [*]  "}` => `"  [*]  "`
+- `{"\u00A0"}` => no-break space (`\uHHHH`)
+- `{"\U01F431"}` => `"🐱"` (`\UHHHHHH`)
+- `{"\"\\"}` => `"\"\\"` (`\"` and `\\`)
+
+```rust
+use glossa_dsl::{error::ResolverResult, Resolver};
+
+fn main() -> ResolverResult<()> {
+  let resolver: Resolver = [
+    ("set", r#"{"{"} {$a}, {$b} {"}"}"#),
+    ("distance", r#"{$num}{"\u00A0"}km"#),
+  ]
+  .try_into()?;
+
+  let result = resolver.get_with_context("set", &[("a", "1"), ("b", "2")])?;
+  assert_eq!(result, "{ 1, 2 }");
+
+  let result = resolver.get_with_context("distance", &[("num", "42")])?;
+  assert_eq!(result, "42\u{A0}km");
+  Ok(())
+}
+```
+
+### Whitespace
+
+- A text value keeps its whitespace as-is.
+- A value is a selector if it starts with `$param ->` after trimming.
+- A branch starts after the whitespace that follows `[key]`, and ends at the
+  end of the line.
+- `{{ ... }}` trims the escaped content.
+- `{"..."}` keeps its content exactly, e.g., `[key] {"  "}indented`.
+- `{~` trims the whitespace at the end of the preceding text, and `~}` trims
+  the whitespace at the start of the following text.
+
+```rust
+use glossa_dsl::{error::ResolverResult, Resolver};
+
+fn main() -> ResolverResult<()> {
+  let resolver: Resolver = [(
+    "point",
+    "(
+      {~ $x ~} ,
+      {~ $y ~}
+    )",
+  )]
+  .try_into()?;
+
+  let result = resolver.get_with_context("point", &[("x", "1"), ("y", "2")])?;
+  assert_eq!(result, "(1,2)");
+  Ok(())
+}
+```
+
+### Parameterised References
+
+A reference can pass arguments to the referenced message:
+`{ time-period(period: $when) }`.
+
+- `name: $param` forwards a parameter of the caller's context.
+- `name: "text"` and `name: 42` pass literals.
+
+The arguments shadow the caller's parameters of the same name, while the
+other parameters of the caller are still visible.
+
+```rust
+use glossa_dsl::{error::ResolverResult, Resolver};
+
+fn main() -> ResolverResult<()> {
+  let resolver: Resolver = [
+    (
+      "time-period",
+      "$period ->
+        [morning] Good Morning
+        *[other] Good {$period}",
+    ),
+    ("greeting", "{ time-period(period: $when) }, { $name }!"),
+    ("morning", r#"{ time-period(period: "morning") }"#),
+  ]
+  .try_into()?;
+
+  let ctx = [("name", "Alice"), ("when", "evening")];
+
+  let result = resolver.get_with_context("greeting", &ctx)?;
+  assert_eq!(result, "Good evening, Alice!");
+
+  assert_eq!(resolver.try_get("morning")?, "Good Morning");
+  Ok(())
+}
+```
 */
 extern crate alloc;
 
 pub mod error;
+pub mod escape;
 pub use error::{ResolverError as Error, ResolverResult as Result};
 
+#[cfg(feature = "formatter")]
+pub mod formatter;
+
+#[cfg(feature = "loader")]
+pub mod loader;
+
+#[cfg(feature = "reload")]
+pub mod reload;
+
 mod parsers;
 pub(crate) mod part;
+mod printer;
+pub mod pseudo;
 
 pub mod resolver;
 pub(crate) use resolver::MiniStr;
 pub use resolver::Resolver;
+#[cfg(feature = "shared")]
+pub use resolver::SharedResolver;
 
 #[cfg(feature = "std")]
 pub type ContextMap<'a> = ahash::HashMap<&'a str, &'a str>;
@@ -0,0 +1,265 @@
+//! Loader for per-locale translation trees.
+//!
+//! ## Layout
+//!
+//! ```text
+//! locales
+//! ├── en
+//! │   ├── main.toml
+//! │   └── settings
+//! │       └── network.json
+//! ├── zh
+//! │   └── main.toml
+//! └── de.toml
+//! ```
+//!
+//! - Every sub-directory is a locale, and all files in it (recursively)
+//!   are merged into one [Resolver].
+//! - A file directly in the root directory is a locale on its own, named
+//!   after its file stem.
+//! - Supported files: `*.toml`, `*.json`. Other files are ignored.
+//! - Symbolic links to directories inside a locale are not followed.
+//! - Nested tables are flattened into dotted keys, see [read_raw_file].
+//!
+//! The same key in two files of one locale is an error
+//! ([ResolverError::DuplicateKey]).
+use std::{
+  collections::BTreeMap,
+  fs,
+  path::{Path, PathBuf},
+};
+
+use compact_str::format_compact;
+use kstring::KString;
+use tap::{Pipe, Tap};
+
+use crate::{
+  MiniStr, Resolver,
+  error::{ResolverError, ResolverResult},
+  parsers::parse_value_or_map_err,
+  resolver::{AST, BTreeRawMap, BidiIsolation},
+};
+
+/// Language tag => Resolver
+pub type Locales = BTreeMap<KString, Resolver>;
+
+/// Loads every locale of a directory.
+///
+/// References are not validated, see [Resolver::validate].
+///
+/// The resolvers of right-to-left locales isolate parameters by default, see
+/// [BidiIsolation::for_locale].
+///
+/// ## Example
+///
+/// ```no_run
+/// use glossa_dsl::loader::load_locales_dir;
+///
+/// let locales = load_locales_dir("locales")?;
+///
+/// for (lang, resolver) in &locales {
+///   resolver.validate()?;
+///   println!("{lang}: {} messages", resolver.len());
+/// }
+///
+/// # Ok::<(), glossa_dsl::Error>(())
+/// ```
+pub fn load_locales_dir<P: AsRef<Path>>(dir: P) -> ResolverResult<Locales> {
+  let dir = dir.as_ref();
+  let in_dir = |e| ResolverError::InFile(dir.into(), Box::new(e));
+
+  let mut locales = BTreeMap::<KString, Vec<PathBuf>>::new();
+
+  for path in sorted_entries(dir).map_err(in_dir)? {
+    let files = match path.is_dir() {
+      true => list_translation_files(&path)?,
+      _ if is_translation_file(&path) => vec![path.clone()],
+      _ => continue,
+    };
+
+    let Some(lang) = path
+      .file_stem()
+      .and_then(|s| s.to_str())
+    else {
+      continue;
+    };
+
+    locales
+      .entry(KString::from_ref(lang))
+      .or_default()
+      .extend(files)
+  }
+
+  locales
+    .into_iter()
+    .map(|(lang, files)| {
+      load_files(&files)
+        .map(|res| res.with_bidi_isolation(BidiIsolation::for_locale(&lang)))
+        .map(|res| (lang, res))
+    })
+    .collect()
+}
+
+/// Merges translation files into one resolver.
+///
+/// A key that is defined in more than one file is an error.
+pub fn load_files<P: AsRef<Path>>(files: &[P]) -> ResolverResult<Resolver> {
+  let mut sources = BTreeMap::<KString, &Path>::new();
+  let mut ast = AST::default();
+
+  for file in files.iter().map(AsRef::as_ref) {
+    let in_file = |e| ResolverError::InFile(file.into(), Box::new(e));
+
+    for (key, value) in read_raw_file(file)? {
+      if let Some(first) = sources.insert(KString::from_ref(&key), file) {
+        return ResolverError::DuplicateKey(key, first.into(), file.into())
+          .pipe(Err);
+      }
+
+      parse_value_or_map_err(&key, &value)
+        .map_err(in_file)?
+        .pipe(|tmpl| ast.insert(KString::from_ref(&key), tmpl));
+    }
+  }
+
+  Ok(Resolver::from(ast))
+}
+
+/// Reads the raw messages of a `*.toml` or `*.json` file, without parsing
+/// them.
+///
+/// Nested tables (objects in JSON) are flattened into dotted keys, e.g.,
+/// `[settings] title = "..."` => `settings.title`. Any value other than a
+/// string or a table is an error.
+pub fn read_raw_file<P: AsRef<Path>>(file: P) -> ResolverResult<BTreeRawMap> {
+  let file = file.as_ref();
+  let in_file = |e| ResolverError::InFile(file.into(), Box::new(e));
+
+  let text = fs::read_to_string(file)
+    .map_err(ResolverError::from)
+    .map_err(in_file)?;
+
+  let mut raw = BTreeRawMap::new();
+  match extension(file) {
+    Some("toml") => toml::from_str::<toml::Table>(&text)
+      .map_err(ResolverError::from)
+      .and_then(|table| flatten_toml(table, "", &mut raw)),
+    Some("json") => serde_json::from_str::<JsonMap>(&text)
+      .map_err(ResolverError::from)
+      .and_then(|map| flatten_json(map, "", &mut raw)),
+    _ => "Unsupported file type, expected *.toml or *.json"
+      .pipe(|msg| ResolverError::ParseError(msg.into()))
+      .pipe(Err),
+  }
+  .map(|_| raw)
+  .map_err(in_file)
+}
+
+type JsonMap = serde_json::Map<String, serde_json::Value>;
+
+fn flatten_toml(
+  table: toml::Table,
+  prefix: &str,
+  raw: &mut BTreeRawMap,
+) -> ResolverResult<()> {
+  table
+    .into_iter()
+    .try_for_each(|(key, value)| {
+      let key = join_key(prefix, &key);
+      match value {
+        toml::Value::String(s) => insert_raw(raw, key, s),
+        toml::Value::Table(t) => flatten_toml(t, &key, raw),
+        _ => expected_string(key),
+      }
+    })
+}
+
+fn flatten_json(
+  map: JsonMap,
+  prefix: &str,
+  raw: &mut BTreeRawMap,
+) -> ResolverResult<()> {
+  map
+    .into_iter()
+    .try_for_each(|(key, value)| {
+      let key = join_key(prefix, &key);
+      match value {
+        serde_json::Value::String(s) => insert_raw(raw, key, s),
+        serde_json::Value::Object(m) => flatten_json(m, &key, raw),
+        _ => expected_string(key),
+      }
+    })
+}
+
+fn join_key(prefix: &str, key: &str) -> MiniStr {
+  match prefix {
+    "" => key.into(),
+    p => format_compact!("{p}.{key}"),
+  }
+}
+
+/// `a.b = ".."` and `[a] b = ".."` are the same key.
+fn insert_raw(
+  raw: &mut BTreeRawMap,
+  key: MiniStr,
+  value: String,
+) -> ResolverResult<()> {
+  match raw.contains_key(&key) {
+    true => ResolverError::ConflictingKey(key).pipe(Err),
+    _ => {
+      raw.insert(key, value.into());
+      Ok(())
+    }
+  }
+}
+
+fn expected_string(key: MiniStr) -> ResolverResult<()> {
+  format_compact!("'{key}': expected a string or a table")
+    .pipe(ResolverError::ParseError)
+    .pipe(Err)
+}
+
+/// Lists the `*.toml` and `*.json` files of a directory (recursively), in
+/// path order.
+///
+/// Symbolic links to directories below `dir` are not followed, so a link
+/// back to a parent cannot recurse forever. Links to files are listed.
+pub fn list_translation_files<P: AsRef<Path>>(
+  dir: P,
+) -> ResolverResult<Vec<PathBuf>> {
+  let dir = dir.as_ref();
+  let in_dir = |e| ResolverError::InFile(dir.into(), Box::new(e));
+
+  sorted_entries(dir)
+    .map_err(in_dir)?
+    .into_iter()
+    .try_fold(Vec::new(), |mut files, path| {
+      let is_dir = fs::symlink_metadata(&path)
+        .map_err(|e| in_dir(e.into()))?
+        .is_dir();
+      match is_dir {
+        true => files.extend(list_translation_files(&path)?),
+        _ if is_translation_file(&path) => files.push(path),
+        _ => {}
+      }
+      Ok(files)
+    })
+}
+
+fn sorted_entries(dir: &Path) -> ResolverResult<Vec<PathBuf>> {
+  fs::read_dir(dir)?
+    .map(|entry| entry.map(|e| e.path()))
+    .collect::<Result<Vec<_>, _>>()?
+    .tap_mut(|paths| paths.sort())
+    .pipe(Ok)
+}
+
+fn extension(path: &Path) -> Option<&str> {
+  path
+    .extension()
+    .and_then(|ext| ext.to_str())
+}
+
+fn is_translation_file(path: &Path) -> bool {
+  path.is_file() && matches!(extension(path), Some("toml" | "json"))
+}
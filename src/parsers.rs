@@ -1,21 +1,24 @@
 mod branch;
 pub(crate) mod context;
-mod process_tmpl;
+pub(crate) mod process_tmpl;
 
 use alloc::{boxed::Box, vec::Vec};
 
 use compact_str::format_compact;
 use nom::{
   IResult, Parser,
+  branch::alt,
   bytes::complete::{tag, take_while1},
-  character::complete::multispace0,
+  character::complete::{char, multispace0},
   multi::many0,
+  sequence::preceded,
 };
 use tap::Pipe;
 
 use crate::{
   error::{ResolverError, ResolverResult},
-  selector, template,
+  selector::{self, CommentAnchor, SelectorInput},
+  template,
 };
 
 pub(crate) fn parse_value_or_map_err<D: core::fmt::Display>(
@@ -29,47 +32,128 @@ pub(crate) fn parse_value_or_map_err<D: core::fmt::Display>(
 }
 
 fn parse_value(input: &str) -> ResolverResult<template::Template> {
-  match parse_conditional(input.trim_ascii()) {
-    Ok((_, cond)) => cond.pipe(template::Template::Conditional),
-    _ => template::parse_template(input)?.pipe(template::Template::Parts),
+  let trimmed = input.trim_ascii();
+
+  match parse_conditional(trimmed) {
+    Ok((_, cond)) if cond.input == SelectorInput::Parameter => {
+      cond.pipe(template::Template::Conditional)
+    }
+    _ => match parse_braced_selector(trimmed) {
+      Some(cond) => cond.pipe(template::Template::Conditional),
+      _ => template::parse_template(input)?.pipe(template::Template::Parts),
+    },
   }
   .pipe(Ok)
 }
 
+/// Parses a selector on a reference, e.g., `{ -brand.gender -> ... }`.
+///
+/// The closing brace may end the line of the last branch, so it is removed
+/// before the branches are parsed. It is only a selector if it has branches,
+/// and nothing is left after them.
+fn parse_braced_selector(input: &str) -> Option<selector::Selector> {
+  let body = input
+    .strip_suffix('}')?
+    .trim_end();
+
+  match parse_conditional(body) {
+    Ok((rest, cond))
+      if cond.input == SelectorInput::Reference
+        && rest.trim().is_empty()
+        && (!cond.cases.is_empty() || cond.default.is_some()) =>
+    {
+      Some(cond)
+    }
+    _ => None,
+  }
+}
+
+/// A line in the body of a selector
+enum SelectorLine<'a> {
+  Comment(&'a str),
+  Branch(branch::Branch),
+}
+
 fn parse_conditional(input: &str) -> IResult<&str, selector::Selector> {
-  let (input, _) = tag("$").parse(input)?;
-  let (input, param) =
-    take_while1(|c: char| c.is_alphanumeric() || c == '-' || c == '_')
-      .parse(input)?;
+  let (input, leading_comments) = many0(branch::parse_comment_line).parse(input)?;
+  let (input, _) = multispace0(input)?;
+  let (input, (input_kind, param)) = alt((
+    preceded(
+      tag("$"),
+      take_while1(|c: char| c.is_alphanumeric() || c == '-' || c == '_'),
+    )
+    .map(|param| (SelectorInput::Parameter, param)),
+    // Message key in braces, e.g., `{ -brand.gender ->`
+    preceded(
+      (char('{'), multispace0),
+      take_while1(|c: char| c.is_alphanumeric() || matches!(c, '-' | '_' | '.')),
+    )
+    .map(|key| (SelectorInput::Reference, key)),
+  ))
+  .parse(input)?;
 
   let (input, _t) = (multispace0, tag("->"), multispace0).parse(input)?;
 
-  let (input, branches) = many0(branch::parse_branch).parse(input)?;
+  let (input, lines) = many0(alt((
+    branch::parse_comment_line.map(SelectorLine::Comment),
+    branch::parse_branch.map(SelectorLine::Branch),
+  )))
+  .parse(input)?;
+
+  let mut comments = leading_comments
+    .into_iter()
+    .map(|c| (CommentAnchor::Head, c.into()))
+    .collect::<Vec<_>>();
 
-  let (cases, default) = branches //
+  // Notes are stored with the branch that follows them.
+  let mut pending = Vec::new();
+  let (cases, default) = lines //
     .into_iter()
     .fold(
       (Vec::with_capacity(8), None),
-      |(mut cases, mut default), branch| {
-        match branch.is_default {
-          true => {
-            default = branch
-              .template
-              .pipe(Box::new)
-              .pipe(Some)
+      |(mut cases, mut default), line| {
+        let anchor = match line {
+          SelectorLine::Comment(c) => {
+            pending.push(c);
+            return (cases, default);
+          }
+          SelectorLine::Branch(branch) if branch.is_default => {
+            default = Some((branch.value, branch.template));
+            CommentAnchor::Default
           }
-          _ => cases.push((branch.value, branch.template)),
-        }
+          SelectorLine::Branch(branch) => {
+            cases.push((branch.value, branch.template));
+            CommentAnchor::Case(cases.len() - 1)
+          }
+        };
+        pending
+          .drain(..)
+          .map(|c| (anchor, c.into()))
+          .pipe(|iter| comments.extend(iter));
         (cases, default)
       },
     );
+  pending
+    .into_iter()
+    .map(|c| (CommentAnchor::End, c.into()))
+    .pipe(|iter| comments.extend(iter));
+  // The default branch is written last.
+  comments.sort_by_key(|(anchor, _)| *anchor);
+
+  let (default_key, default) = match default {
+    Some((key, tmpl)) => (key, tmpl.pipe(Box::new).pipe(Some)),
+    _ => Default::default(),
+  };
 
   (
     input,
     selector::Selector {
       param: param.into(),
+      input: input_kind,
       cases: cases.into(),
       default,
+      default_key,
+      comments: comments.into(),
     },
   )
     .pipe(Ok)
@@ -1,7 +1,7 @@
 use nom::{
   IResult, Parser,
   bytes::complete::{tag, take_till, take_until},
-  character::complete::multispace0,
+  character::complete::{char, multispace0},
   combinator::opt,
   sequence::delimited,
 };
@@ -30,9 +30,10 @@ pub(crate) fn parse_branch(input: &str) -> IResult<&str, Branch> {
   let (input, _) = multispace0(input)?;
   let (input, content) = take_till(|c| c == '\n' || c == '\r').parse(input)?;
 
+  // A branch that fails to parse must not silently end the selector.
   let template = parsers::parse_value(content).map_err(|_e| {
     nom::error::Error::new(content, nom::error::ErrorKind::Verify)
-      .pipe(nom::Err::Error)
+      .pipe(nom::Err::Failure)
   })?;
 
   Ok((
@@ -44,3 +45,11 @@ pub(crate) fn parse_branch(input: &str) -> IResult<&str, Branch> {
     },
   ))
 }
+
+/// Parses a `# ...` line of a selector, and returns the trimmed note.
+pub(crate) fn parse_comment_line(input: &str) -> IResult<&str, &str> {
+  let (input, _) = (multispace0, char('#')).parse(input)?;
+  let (input, comment) = take_till(|c| c == '\n' || c == '\r').parse(input)?;
+
+  Ok((input, comment.trim()))
+}
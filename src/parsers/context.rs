@@ -1,7 +1,8 @@
 use crate::resolver::BTreeRawMap;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub(crate) enum Context<'a> {
+  #[default]
   Empty,
   Slice(&'a [(&'a str, &'a str)]),
   BTree(&'a BTreeRawMap),
@@ -9,12 +10,9 @@ pub(crate) enum Context<'a> {
   Map(&'a crate::ContextMap<'a>),
   #[cfg(feature = "std")]
   MapBuf(&'a crate::ContextMapBuf),
-}
-
-impl Default for Context<'_> {
-  fn default() -> Self {
-    Self::Empty
-  }
+  /// Call-site arguments (sorted by key) layered over the caller's
+  /// context.
+  Scoped(&'a [(&'a str, &'a str)], &'a Context<'a>),
 }
 
 impl<'a> Context<'a> {
@@ -31,6 +29,9 @@ impl<'a> Context<'a> {
       Self::MapBuf(context) => context
         .get(key)
         .map(|v| v.as_str()),
+      Self::Scoped(args, parent) => {
+        Self::get_slice_value(args, key).or_else(|| parent.get_value(key))
+      }
     }
   }
 
@@ -1,103 +1,480 @@
-use alloc::borrow::ToOwned;
+use alloc::{borrow::Cow, vec::Vec};
+use core::cell::{Cell, RefCell};
 
-use tap::Pipe;
+use compact_str::format_compact;
+use tap::Tap;
+use tinyvec::TinyVec;
 
 use crate::{
   MiniStr,
   error::{ResolverError, ResolverResult},
+  escape::Escaper,
   parsers::context::Context,
-  part::{TemplatePart, VariableRef},
-  resolver::Resolver,
-  selector, template,
+  part::{Call, CallArg, Markup, TemplatePart, VariableRef},
+  resolver::{
+    BidiIsolation, EscapeMode, MissingKeyPolicy, MissingParameterPolicy,
+    ResolveOptions, Resolved, Resolver, validate::cyclic_reference,
+  },
+  selector::{self, SelectorInput},
+  template,
 };
 
+/// State of a single resolution: the options, and what has been substituted
+/// according to them.
+pub(crate) struct Processor<'r> {
+  resolver: &'r Resolver,
+  options: &'r ResolveOptions<'r>,
+  /// Records errors and renders a substitute, instead of failing
+  lossy: bool,
+  /// What has been substituted, in order of first use
+  missing: RefCell<Missing>,
+  /// Keys of the messages being processed, to detect cyclic references
+  stack: RefCell<Vec<MiniStr>>,
+  /// Set while the input of a selector on a reference is rendered. It is
+  /// compared with the keys of the branches, so it is neither escaped nor
+  /// isolated.
+  plain: Cell<bool>,
+}
+
+#[derive(Default)]
+struct Missing {
+  parameters: Vec<MiniStr>,
+  keys: Vec<MiniStr>,
+  errors: Vec<ResolverError>,
+}
+
+fn push_unique(names: &mut Vec<MiniStr>, name: &str) {
+  if !names.iter().any(|x| x == name) {
+    names.push(name.into())
+  }
+}
+
+/// Receives the rendered pieces of a message, in order
+pub(crate) trait Output {
+  /// Text of a message (already escaped), or a substitute
+  fn text(&mut self, text: &str);
+  /// Value of `{ $name }` (already escaped and isolated)
+  fn parameter(&mut self, name: &str, value: &str);
+  /// Markup tag, and its (already escaped) source, e.g., `<b>`
+  fn markup(&mut self, markup: &Markup, source: &str);
+  /// Start of the text of a referenced message
+  fn enter_reference(&mut self, _key: &str) {}
+  /// End of the text of a referenced message
+  fn exit_reference(&mut self, _key: &str) {}
+}
+
+/// Flat text output
+impl Output for MiniStr {
+  fn text(&mut self, text: &str) {
+    self.push_str(text)
+  }
+
+  fn parameter(&mut self, _name: &str, value: &str) {
+    self.push_str(value)
+  }
+
+  fn markup(&mut self, _markup: &Markup, source: &str) {
+    self.push_str(source)
+  }
+}
+
 impl Resolver {
-  pub(crate) fn process_template(
+  /// Processes the template of `var_name` with the default options, and the
+  /// settings of the resolver, e.g., [Resolver::missing_key_policy].
+  pub(crate) fn try_get_template_and_process(
     &self,
-    template: &template::Template,
+    var_name: &str,
     context: &Context<'_>,
   ) -> ResolverResult<MiniStr> {
+    let options = ResolveOptions {
+      bidi_isolation: self.bidi_isolation(),
+      missing_key: self.missing_key_policy(),
+      ..Default::default()
+    };
+    let mut text = MiniStr::const_new("");
+    Processor::new(self, &options)
+      .try_get_template_and_process(var_name, context, &mut text)?;
+    Ok(text)
+  }
+}
+
+impl<'r> Processor<'r> {
+  pub(crate) fn new(
+    resolver: &'r Resolver,
+    options: &'r ResolveOptions<'r>,
+  ) -> Self {
+    Self {
+      resolver,
+      options,
+      lossy: false,
+      missing: RefCell::default(),
+      stack: RefCell::default(),
+      plain: Cell::new(false),
+    }
+  }
+
+  /// Never fails: errors are collected, and a substitute is rendered
+  /// instead.
+  pub(crate) fn lossy(self) -> Self {
+    Self {
+      lossy: true,
+      ..self
+    }
+  }
+
+  pub(crate) fn into_resolved(self, text: MiniStr) -> Resolved {
+    self.into_lossy_resolved(text).0
+  }
+
+  pub(crate) fn into_lossy_resolved(
+    self,
+    text: MiniStr,
+  ) -> (Resolved, Vec<ResolverError>) {
+    let Missing {
+      parameters,
+      keys,
+      errors,
+    } = self.missing.into_inner();
+
+    let resolved = Resolved {
+      text,
+      missing_parameters: parameters,
+      missing_keys: keys,
+    };
+    (resolved, errors)
+  }
+
+  /// Fails with `err`; or, in lossy mode, records it and returns the
+  /// substitute.
+  fn recover(
+    &self,
+    err: ResolverError,
+    substitute: impl FnOnce() -> MiniStr,
+  ) -> ResolverResult<MiniStr> {
+    if !self.lossy {
+      return Err(err);
+    }
+    self
+      .missing
+      .borrow_mut()
+      .errors
+      .push(err);
+    Ok(substitute())
+  }
+
+  /// [ResolveOptions::escape], unless a selector input is being rendered
+  fn escape_mode(&self) -> EscapeMode<'r> {
+    match self.plain.get() {
+      true => EscapeMode::Off,
+      _ => self.options.escape,
+    }
+  }
+
+  /// [ResolveOptions::bidi_isolation], unless a selector input is being
+  /// rendered
+  fn bidi_isolation(&self) -> BidiIsolation {
+    match self.plain.get() {
+      true => BidiIsolation::Off,
+      _ => self.options.bidi_isolation,
+    }
+  }
+
+  pub(crate) fn process_template<O: Output>(
+    &self,
+    template: &template::Template,
+    context: &Context<'_>,
+    out: &mut O,
+  ) -> ResolverResult<()> {
     use template::Template::*;
     match template {
-      Conditional(x) => self.process_tmpl_selector(context, x),
-      Parts(parts) => self.process_tmpl_parts(context, parts),
+      Conditional(x) => self.process_tmpl_selector(context, x, out),
+      Parts(parts) => self.process_tmpl_parts(context, parts, out),
     }
   }
 
-  pub(crate) fn process_tmpl_parts(
+  pub(crate) fn process_tmpl_parts<O: Output>(
     &self,
     context: &Context<'_>,
     parts: &[TemplatePart],
-  ) -> Result<MiniStr, ResolverError> {
-    parts.iter().try_fold(
-      MiniStr::const_new(""), //
-      |mut result, part| {
-        let push_str = |s| {
-          result.push_str(s);
-          Ok(result)
-        };
+    out: &mut O,
+  ) -> ResolverResult<()> {
+    let (text_escaper, param_escaper) = match self.escape_mode() {
+      EscapeMode::Off => (None, None),
+      EscapeMode::Parameters(escaper) => (None, Some(escaper)),
+      EscapeMode::Text(escaper) => (Some(escaper), None),
+    };
+
+    parts
+      .iter()
+      .try_for_each(|part| {
         match part {
-          TemplatePart::Text(text) => push_str(text),
-          TemplatePart::Variable(var) => match var {
-            VariableRef::Variable(var_name) => self
-              .try_get_template_and_process(var_name, context)?
-              .pipe_deref(push_str),
-            VariableRef::Parameter(param) => {
-              let err = || {
-                param
-                  .to_owned()
-                  .pipe(ResolverError::MissingParameter)
-              };
-              context
-                .get_value(param)
-                .ok_or_else(err)?
-                .pipe(push_str)
+          TemplatePart::Text(text) => out.text(&escape(text, text_escaper)),
+          TemplatePart::Comment(_) => {}
+          TemplatePart::Markup(markup) => {
+            let source = format_compact!("{markup}");
+            out.markup(markup, &escape(&source, text_escaper))
+          }
+          TemplatePart::Variable(VariableRef::Parameter(param)) => {
+            let value = self.get_param(context, param)?;
+            match self.isolates(part) {
+              true => {
+                let mut isolated = MiniStr::default();
+                isolated.push(BidiIsolation::FSI);
+                isolated.push_str(&escape(&value, param_escaper));
+                isolated.push(BidiIsolation::PDI);
+                out.parameter(param, &isolated)
+              }
+              _ => out.parameter(param, &escape(&value, param_escaper)),
             }
-          },
+          }
+          // The text of references is escaped by their own parts.
+          TemplatePart::Variable(VariableRef::Variable(var_name)) => self
+            .process_reference(part, var_name, out, |out| {
+              self.try_get_template_and_process(var_name, context, out)
+            })?,
+          TemplatePart::Variable(VariableRef::Call(call)) => self
+            .process_reference(part, &call.name, out, |out| {
+              self.process_tmpl_call(context, call, out)
+            })?,
         }
-      },
-    )
+        Ok(())
+      })
+  }
+
+  /// Whether the value of the placeable is wrapped in isolation marks,
+  /// according to [ResolveOptions::bidi_isolation]
+  fn isolates(&self, part: &TemplatePart) -> bool {
+    use BidiIsolation::*;
+    match (self.bidi_isolation(), part) {
+      (Off, _) => false,
+      (Parameters, TemplatePart::Variable(VariableRef::Parameter(_))) => true,
+      (Parameters, _) => false,
+      (ParametersAndReferences, _) => true,
+    }
   }
 
+  /// Marks what `f` renders as the text of the referenced message `key`, and
+  /// wraps it in isolation marks if the placeable [isolates](Self::isolates).
+  fn process_reference<O: Output>(
+    &self,
+    part: &TemplatePart,
+    key: &str,
+    out: &mut O,
+    f: impl FnOnce(&mut O) -> ResolverResult<()>,
+  ) -> ResolverResult<()> {
+    let isolate = self.isolates(part);
+    out.enter_reference(key);
+    if isolate {
+      out.text(BidiIsolation::FSI.encode_utf8(&mut [0; 4]))
+    }
+    f(out)?;
+    if isolate {
+      out.text(BidiIsolation::PDI.encode_utf8(&mut [0; 4]))
+    }
+    out.exit_reference(key);
+    Ok(())
+  }
+
+  /// Processes the template of `var_name`, or handles the missing key
+  /// according to [ResolveOptions::missing_key].
+  ///
   /// old_name: process_ref_var
-  pub(crate) fn try_get_template_and_process(
+  pub(crate) fn try_get_template_and_process<O: Output>(
     &self,
     var_name: &str,
     context: &Context<'_>,
-  ) -> Result<MiniStr, ResolverError> {
-    let var_template = self.try_get_template(var_name)?;
-    self.process_template(var_template, context)
+    out: &mut O,
+  ) -> ResolverResult<()> {
+    use MissingKeyPolicy::*;
+
+    let text = match (self.resolver.get(var_name), self.options.missing_key) {
+      (Some(var_template), _) => {
+        return self.process_message(var_name, var_template, context, out);
+      }
+      (None, Error) => self.undefined_variable(var_name)?,
+      (None, Key) => var_name.into(),
+      (None, Placeholder) => format_compact!("⟦{var_name}⟧"),
+      (None, Fallback(fallback)) => {
+        // Nested references of the fallback message are looked up in the
+        // same way: first `self.resolver`, then `fallback`.
+        let Some(var_template) = fallback.get(var_name) else {
+          self.substitute(&self.undefined_variable(var_name)?, out);
+          return Ok(());
+        };
+        push_unique(&mut self.missing.borrow_mut().keys, var_name);
+        return self.process_message(var_name, var_template, context, out);
+      }
+    };
+    if !matches!(self.options.missing_key, Error | Fallback(_)) {
+      push_unique(&mut self.missing.borrow_mut().keys, var_name);
+    }
+    self.substitute(&text, out);
+    Ok(())
+  }
+
+  /// Renders the substitute of a missing key (or of a cyclic reference) in
+  /// place of the text of the message, i.e., escaped in [EscapeMode::Text].
+  fn substitute<O: Output>(&self, text: &str, out: &mut O) {
+    match self.escape_mode() {
+      EscapeMode::Text(escaper) => out.text(&escape(text, Some(escaper))),
+      _ => out.text(text),
+    }
   }
 
-  pub(crate) fn process_tmpl_selector(
+  /// Processes the template of the message `key`, unless the message is
+  /// already being processed, i.e., it references itself.
+  fn process_message<O: Output>(
     &self,
+    key: &str,
+    template: &template::Template,
     context: &Context<'_>,
-    selector: &selector::Selector,
-  ) -> Result<MiniStr, ResolverError> {
-    let new_err = |missing_param| {
-      use crate::error::ResolverError::*;
-      selector
-        .param
-        .to_owned()
-        .pipe(match missing_param {
-          true => MissingParameter,
-          _ => NoDefaultBranch,
+    out: &mut O,
+  ) -> ResolverResult<()> {
+    let cycle = {
+      let stack = self.stack.borrow();
+      stack
+        .iter()
+        .any(|k| k == key)
+        .then(|| {
+          let path = stack
+            .iter()
+            .map(MiniStr::as_str)
+            .collect::<Vec<_>>();
+          cyclic_reference(&path, key)
         })
     };
+    if let Some(err) = cycle {
+      self.substitute(&self.recover(err, || format_compact!("{{{key}}}"))?, out);
+      return Ok(());
+    }
+
+    self
+      .stack
+      .borrow_mut()
+      .push(key.into());
+    let result = self.process_template(template, context, out);
+    self.stack.borrow_mut().pop();
+    result
+  }
+
+  fn undefined_variable(&self, var_name: &str) -> ResolverResult<MiniStr> {
+    self.recover(ResolverError::UndefinedVariable(var_name.into()), || {
+      format_compact!("{{{var_name}}}")
+    })
+  }
 
-    let param_value = context
-      .get_value(&selector.param)
-      .ok_or_else(|| new_err(true))?;
+  /// Resolves the call-site arguments against the caller's context, then
+  /// processes the referenced template within the scoped context.
+  pub(crate) fn process_tmpl_call<O: Output>(
+    &self,
+    context: &Context<'_>,
+    call: &Call,
+    out: &mut O,
+  ) -> ResolverResult<()> {
+    let values = call
+      .args
+      .iter()
+      .map(|(name, arg)| match arg {
+        CallArg::Literal(value) => {
+          Ok((name.as_str(), Cow::Borrowed(value.as_str())))
+        }
+        CallArg::Parameter(param) => self
+          .get_param(context, param)
+          .map(|value| (name.as_str(), value)),
+      })
+      .collect::<Result<TinyVec<[(&str, Cow<str>); 5]>, _>>()?;
+
+    let args = values
+      .iter()
+      .map(|(name, value)| (*name, value.as_ref()))
+      .collect::<TinyVec<[(&str, &str); 5]>>()
+      .tap_mut(|x| x.sort_unstable_by_key(|&(k, _)| k));
+
+    let scoped = Context::Scoped(&args, context);
+    self.try_get_template_and_process(&call.name, &scoped, out)
+  }
+
+  pub(crate) fn process_tmpl_selector<O: Output>(
+    &self,
+    context: &Context<'_>,
+    selector: &selector::Selector,
+    out: &mut O,
+  ) -> ResolverResult<()> {
+    let param_value = match selector.input {
+      SelectorInput::Parameter => self.get_param(context, &selector.param)?,
+      // Only the missing-parameter and missing-key policies apply to the
+      // text that is compared with the keys.
+      SelectorInput::Reference => {
+        let mut text = MiniStr::const_new("");
+        let plain = self.plain.replace(true);
+        let result =
+          self.try_get_template_and_process(&selector.param, context, &mut text);
+        self.plain.set(plain);
+        result?;
+        Cow::Owned(text.into_string())
+      }
+    };
 
     for (value, case_template) in &selector.cases {
-      if value == param_value {
-        return self.process_template(case_template, context);
+      if value == &*param_value {
+        return self.process_template(case_template, context, out);
       }
     }
 
     match &selector.default {
-      Some(default) => self.process_template(default, context),
-      _ => new_err(false).pipe(Err),
+      Some(default) => self.process_template(default, context, out),
+      _ => self
+        .recover(
+          ResolverError::NoDefaultBranch(selector.param.clone()),
+          MiniStr::default,
+        )
+        .map(|_| ()),
+    }
+  }
+
+  /// Gets the value of a parameter, or a substitute according to
+  /// [ResolveOptions::missing_parameter].
+  fn get_param<'c>(
+    &self,
+    context: &'c Context<'_>,
+    name: &str,
+  ) -> ResolverResult<Cow<'c, str>> {
+    if let Some(value) = context.get_value(name) {
+      return Ok(Cow::Borrowed(value));
+    }
+
+    use MissingParameterPolicy::*;
+    let value = match self.options.missing_parameter {
+      Error => {
+        return self
+          .recover(ResolverError::MissingParameter(name.into()), || {
+            format_compact!("{{${name}}}")
+          })
+          .map(|value| Cow::Owned(value.into_string()));
+      }
+      Literal => format_compact!("{{${name}}}"),
+      Empty => MiniStr::const_new(""),
+      Callback(f) => f(name),
+    };
+
+    push_unique(
+      &mut self
+        .missing
+        .borrow_mut()
+        .parameters,
+      name,
+    );
+    Ok(Cow::Owned(value.into_string()))
+  }
+}
+
+fn escape<'s>(text: &'s str, escaper: Option<&dyn Escaper>) -> Cow<'s, str> {
+  match escaper {
+    Some(escaper) => {
+      let mut escaped = MiniStr::default();
+      escaper.escape(text, &mut escaped);
+      Cow::Owned(escaped.into_string())
     }
+    _ => Cow::Borrowed(text),
   }
 }
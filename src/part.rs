@@ -1,3 +1,5 @@
+use alloc::boxed::Box;
+
 use crate::MiniStr;
 
 /// Template segment variants
@@ -11,6 +13,14 @@ use crate::MiniStr;
 pub enum TemplatePart {
   Text(MiniStr),
   Variable(VariableRef),
+  /// Translator note, e.g., `{# formal tone #}`
+  ///
+  /// It is kept in the AST, but renders as nothing.
+  Comment(MiniStr),
+  /// Markup tag, e.g., `<link>`
+  ///
+  /// Boxed to keep [TemplatePart] small.
+  Markup(Box<Markup>),
 }
 
 impl Default for TemplatePart {
@@ -25,6 +35,60 @@ impl Default for TemplatePart {
 pub enum VariableRef {
   Variable(MiniStr),
   Parameter(MiniStr),
+  /// Reference with call-site arguments, e.g.,
+  /// `{ time-period(period: $when) }`
+  ///
+  /// Boxed to keep [TemplatePart] small.
+  Call(Box<Call>),
+}
+
+/// A parameterised reference to another message
+///
+/// The arguments create a scoped context for the referenced template:
+/// they shadow the caller's parameters of the same name, while the other
+/// parameters of the caller are still visible.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Call {
+  /// Key of the referenced message
+  pub(crate) name: MiniStr,
+  /// (Argument name, Argument value) pairs
+  pub(crate) args: Box<[(MiniStr, CallArg)]>,
+}
+
+/// Value passed to a [Call] argument
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CallArg {
+  /// `name: $param` => forwards a parameter of the caller's context
+  Parameter(MiniStr),
+  /// `name: "text"` or `name: 42`
+  Literal(MiniStr),
+}
+
+/// Markup tag in the text of a message
+///
+/// Tags have names only; attributes (e.g., the target of a link) are supplied
+/// by the application. Flat rendering writes the tag back as it is, while
+/// [Resolver::resolve_tree](crate::Resolver::resolve_tree) turns a pair of
+/// tags into an element.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Markup {
+  /// `<name>`
+  Open(MiniStr),
+  /// `</name>`
+  Close(MiniStr),
+  /// `<name/>`
+  Standalone(MiniStr),
+}
+
+impl Markup {
+  pub fn name(&self) -> &str {
+    match self {
+      Self::Open(name) | Self::Close(name) | Self::Standalone(name) => name,
+    }
+  }
 }
 
 // impl Default for VariableRef {
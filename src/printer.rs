@@ -0,0 +1,370 @@
+//! Serializes the AST back into DSL source.
+//!
+//! The output is canonical:
+//!
+//! - Placeables are written with inner spaces, e.g., `{ $name }`
+//! - Text that cannot be written verbatim becomes a string literal, e.g.,
+//!   `{"{"}`
+//! - Branches are indented by two spaces, and the default branch comes last
+//! - An empty branch is written as `[key] {""}`
+//! - A nested selector is written on the line of its branch
+//! - A selector on a reference is wrapped in braces, e.g.,
+//!   `{ -brand.gender -> ... }`
+//! - `# ...` notes of a selector are written on their own lines, before the
+//!   branch they belong to
+//! - Markup tags are written as they are, e.g., `<b>`
+use core::fmt::{self, Display, Formatter, Write};
+
+use compact_str::format_compact;
+use tap::Pipe;
+
+use crate::{
+  MiniStr,
+  error::{ResolverError, ResolverResult},
+  part::{Call, CallArg, Markup, TemplatePart, VariableRef},
+  selector::{CommentAnchor, Selector, SelectorInput},
+  template::{Template, parse_markup},
+};
+
+const INDENT: &str = "  ";
+
+/// Where a template is written
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Layout {
+  /// Value of a message; selectors span multiple lines
+  Block,
+  /// Content of a branch; everything must fit on a single line
+  Line,
+}
+
+impl Display for Template {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    write_template(f, self, Layout::Block)
+  }
+}
+
+impl Display for Selector {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    write_selector(f, self, Layout::Block)
+  }
+}
+
+impl Display for TemplatePart {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    write_part(f, self, None, true, Layout::Block)
+  }
+}
+
+impl Display for Markup {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Open(name) => write!(f, "<{name}>"),
+      Self::Close(name) => write!(f, "</{name}>"),
+      Self::Standalone(name) => write!(f, "<{name}/>"),
+    }
+  }
+}
+
+impl Template {
+  /// Serializes the template into canonical DSL source.
+  ///
+  /// It is the inverse of parsing: parsing the output yields the same
+  /// template.
+  ///
+  /// ## Errors
+  ///
+  /// The content of a branch is a single line. A nested selector with more
+  /// than one branch (or with notes before a branch) cannot be written
+  /// there, so [ResolverError::SourceError] is returned. The parser never
+  /// creates such a selector, but a deserialized AST may contain one.
+  ///
+  /// [Display] never fails: it writes such a selector on multiple lines,
+  /// which is readable, but cannot be parsed back.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use glossa_dsl::Resolver;
+  ///
+  /// let res: Resolver = [
+  ///   ("greeting", "{h}, {$name}!{{ {a} }}"),
+  ///   (
+  ///     "gender",
+  ///     "$attr ->
+  ///          [male] Mr.
+  ///       *[female] Ms.",
+  ///   ),
+  /// ]
+  /// .try_into()?;
+  ///
+  /// assert_eq!(
+  ///   res["greeting"].to_source()?,
+  ///   r#"{ h }, { $name }{"!{a}"}"#
+  /// );
+  ///
+  /// assert_eq!(
+  ///   res["gender"].to_source()?,
+  ///   "$attr ->\n  [male] Mr.\n  *[female] Ms."
+  /// );
+  ///
+  /// # Ok::<(), glossa_dsl::Error>(())
+  /// ```
+  pub fn to_source(&self) -> ResolverResult<MiniStr> {
+    check_nested_selectors(self, false)?;
+    Ok(format_compact!("{self}"))
+  }
+}
+
+impl Selector {
+  /// Serializes the selector into canonical DSL source.
+  ///
+  /// See [Template::to_source] for the errors.
+  pub fn to_source(&self) -> ResolverResult<MiniStr> {
+    branches(self).try_for_each(|tmpl| check_nested_selectors(tmpl, true))?;
+    Ok(format_compact!("{self}"))
+  }
+}
+
+impl TemplatePart {
+  /// Serializes the part into canonical DSL source.
+  pub fn to_source(&self) -> ResolverResult<MiniStr> {
+    Ok(format_compact!("{self}"))
+  }
+}
+
+/// Checks that every nested selector fits on the line of its branch.
+fn check_nested_selectors(
+  template: &Template,
+  is_nested: bool,
+) -> ResolverResult<()> {
+  let Template::Conditional(selector) = template else {
+    return Ok(());
+  };
+  if is_nested && !fits_on_line(selector) {
+    return "a nested selector must fit on a single line"
+      .pipe(MiniStr::from)
+      .pipe(ResolverError::SourceError)
+      .pipe(Err);
+  }
+  branches(selector).try_for_each(|tmpl| check_nested_selectors(tmpl, true))
+}
+
+fn branches(selector: &Selector) -> impl Iterator<Item = &Template> {
+  selector
+    .cases
+    .iter()
+    .map(|(_, tmpl)| tmpl)
+    .chain(selector.default.as_deref())
+}
+
+fn write_template(
+  f: &mut Formatter<'_>,
+  template: &Template,
+  layout: Layout,
+) -> fmt::Result {
+  match template {
+    Template::Conditional(selector) => write_selector(f, selector, layout),
+    Template::Parts(parts) => parts
+      .iter()
+      .enumerate()
+      .try_for_each(|(idx, part)| {
+        let prev = idx
+          .checked_sub(1)
+          .and_then(|i| parts.get(i));
+        let is_last = idx + 1 == parts.len();
+        write_part(f, part, prev, is_last, layout)
+      }),
+  }
+}
+
+fn write_selector(
+  f: &mut Formatter<'_>,
+  selector: &Selector,
+  layout: Layout,
+) -> fmt::Result {
+  // A selector that does not fit on the line of its branch is still
+  // written, on multiple lines; `to_source` rejects it beforehand.
+  let layout = match layout == Layout::Line && !fits_on_line(selector) {
+    true => Layout::Block,
+    _ => layout,
+  };
+  let branch_sep = match layout {
+    Layout::Block => "\n",
+    Layout::Line => " ",
+  };
+
+  let comments_at = |anchor| {
+    selector
+      .comments
+      .iter()
+      .filter(move |(a, _)| *a == anchor)
+      .map(|(_, comment)| comment)
+  };
+  let write_comments = |f: &mut Formatter<'_>, anchor| {
+    comments_at(anchor).try_for_each(|comment| match layout {
+      Layout::Block => write!(f, "\n{INDENT}# {comment}"),
+      Layout::Line => write!(f, " # {comment}"),
+    })
+  };
+
+  if layout == Layout::Block {
+    for comment in comments_at(CommentAnchor::Head) {
+      writeln!(f, "# {comment}")?;
+    }
+  }
+  match selector.input {
+    SelectorInput::Parameter => write!(f, "${} ->", selector.param)?,
+    SelectorInput::Reference => write!(f, "{{ {} ->", selector.param)?,
+  }
+
+  let default = selector
+    .default
+    .as_deref()
+    .map(|tmpl| {
+      (
+        CommentAnchor::Default,
+        "*",
+        selector.default_key.as_str(),
+        tmpl,
+      )
+    });
+
+  selector
+    .cases
+    .iter()
+    .enumerate()
+    .map(|(idx, (key, tmpl))| (CommentAnchor::Case(idx), "", key.as_str(), tmpl))
+    .chain(default)
+    .try_for_each(|(anchor, star, key, tmpl)| {
+      write_comments(f, anchor)?;
+      f.write_str(branch_sep)?;
+      if layout == Layout::Block {
+        f.write_str(INDENT)?;
+      }
+      write!(f, "{star}[{key}] ")?;
+      match is_empty_template(tmpl) {
+        // Otherwise, the next line would be the content of the branch.
+        true => f.write_str(r#"{""}"#),
+        _ => write_template(f, tmpl, Layout::Line),
+      }
+    })?;
+
+  write_comments(f, CommentAnchor::End)?;
+  match (selector.input, layout) {
+    (SelectorInput::Parameter, _) => Ok(()),
+    (SelectorInput::Reference, Layout::Block) => f.write_str("\n}"),
+    (SelectorInput::Reference, Layout::Line) => f.write_str(" }"),
+  }
+}
+
+/// The content of a branch is a single line, so a nested selector can only
+/// have one branch, or a single trailing note. A note would hide the closing
+/// brace of a selector on a reference.
+fn fits_on_line(selector: &Selector) -> bool {
+  let branches = selector.cases.len() + usize::from(selector.default.is_some());
+  let notes = selector.comments.len();
+
+  branches + notes <= 1
+    && (selector.input == SelectorInput::Parameter || notes == 0)
+    && selector
+      .comments
+      .iter()
+      .all(|(anchor, _)| *anchor == CommentAnchor::End)
+}
+
+fn is_empty_template(template: &Template) -> bool {
+  matches!(template, Template::Parts(parts) if parts.is_empty())
+}
+
+fn write_part(
+  f: &mut Formatter<'_>,
+  part: &TemplatePart,
+  prev: Option<&TemplatePart>,
+  is_last: bool,
+  layout: Layout,
+) -> fmt::Result {
+  match part {
+    TemplatePart::Text(text) => match needs_literal(text, prev, is_last, layout) {
+      true => write_literal_placeable(f, text),
+      _ => f.write_str(text),
+    },
+    TemplatePart::Comment(comment) => write!(f, "{{# {comment} #}}"),
+    TemplatePart::Markup(markup) => write!(f, "{markup}"),
+    TemplatePart::Variable(var) => match var {
+      VariableRef::Variable(name) => write!(f, "{{ {name} }}"),
+      VariableRef::Parameter(param) => write!(f, "{{ ${param} }}"),
+      VariableRef::Call(call) => write_call(f, call),
+    },
+  }
+}
+
+/// Checks whether the text cannot be written verbatim.
+fn needs_literal(
+  text: &str,
+  prev: Option<&TemplatePart>,
+  is_last: bool,
+  layout: Layout,
+) -> bool {
+  let is_first = prev.is_none();
+  // `{ key ->` is covered by the check of `{` below.
+  let starts_like_selector = || {
+    text
+      .trim_start()
+      .starts_with(['$', '#'])
+  };
+
+  text.is_empty()
+    || text.contains('{')
+    || contains_markup(text)
+    // Adjacent text parts would be merged into one.
+    || matches!(prev, Some(TemplatePart::Text(_)))
+    || (is_first && starts_like_selector())
+    // The last branch of a message is trimmed.
+    || (layout == Layout::Line
+      && ((is_first && text.starts_with(char::is_whitespace))
+        || (is_last && text.ends_with(char::is_whitespace))
+        || text.contains(['\n', '\r'])))
+}
+
+/// Checks whether the text would be parsed as markup.
+fn contains_markup(text: &str) -> bool {
+  text
+    .match_indices('<')
+    .any(|(idx, _)| parse_markup(&text[idx..]).is_ok())
+}
+
+fn write_literal_placeable(f: &mut Formatter<'_>, text: &str) -> fmt::Result {
+  f.write_char('{')?;
+  write_string_literal(f, text)?;
+  f.write_char('}')
+}
+
+fn write_string_literal(f: &mut Formatter<'_>, text: &str) -> fmt::Result {
+  f.write_char('"')?;
+  for c in text.chars() {
+    match c {
+      '"' => f.write_str(r#"\""#)?,
+      '\\' => f.write_str(r"\\")?,
+      c if c.is_control() => write!(f, r"\u{:04X}", c as u32)?,
+      c => f.write_char(c)?,
+    }
+  }
+  f.write_char('"')
+}
+
+fn write_call(f: &mut Formatter<'_>, call: &Call) -> fmt::Result {
+  write!(f, "{{ {}(", call.name)?;
+
+  for (idx, (name, arg)) in call.args.iter().enumerate() {
+    if idx != 0 {
+      f.write_str(", ")?;
+    }
+    write!(f, "{name}: ")?;
+    match arg {
+      CallArg::Parameter(param) => write!(f, "${param}")?,
+      CallArg::Literal(literal) => write_string_literal(f, literal)?,
+    }
+  }
+
+  f.write_str(") }")
+}
@@ -0,0 +1,220 @@
+//! Pseudo-localization
+//!
+//! A pseudo-locale makes untranslated (hard-coded) strings and truncated
+//! layouts easy to spot before real translations arrive:
+//!
+//! `Hello { $name }!` => `[Ĥéļļö { $name }!~~~]`
+//!
+//! Only the text is changed. Placeables, selector keys and parameter names
+//! stay untouched, so every message still resolves with the same context.
+//! Messages that are the input of a selector (e.g., `-brand.gender` in
+//! `{ -brand.gender -> ... }`), and the messages they reference, are kept as
+//! they are, so that they still match the keys of the branches.
+use alloc::{boxed::Box, vec::Vec};
+
+use compact_str::ToCompactString;
+use tap::Pipe;
+
+use crate::{
+  MiniStr,
+  part::TemplatePart,
+  resolver::{AST, Resolver},
+  selector::{Selector, SelectorInput},
+  template::{Template, TinyTemplateParts},
+};
+
+const ACCENTED_UPPER: [char; 26] = [
+  'Å', 'Ɓ', 'Ç', 'Đ', 'Ê', 'Ƒ', 'Ĝ', 'Ĥ', 'Î', 'Ĵ', 'Ķ', 'Ļ', 'Ṁ', 'Ń', 'Ö', 'Þ',
+  'Ǫ', 'Ŕ', 'Š', 'Ŧ', 'Û', 'Ṽ', 'Ŵ', 'Ẋ', 'Ý', 'Ž',
+];
+const ACCENTED_LOWER: [char; 26] = [
+  'å', 'ƀ', 'ç', 'đ', 'é', 'ƒ', 'ĝ', 'ĥ', 'î', 'ĵ', 'ķ', 'ļ', 'ṁ', 'ñ', 'ö', 'þ',
+  'ǫ', 'ŕ', 'š', 'ŧ', 'û', 'ṽ', 'ŵ', 'ẋ', 'ý', 'ž',
+];
+
+/// Options of the pseudo-locale
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PseudoOptions {
+  /// Replaces ASCII letters with accented look-alikes, e.g., `a` => `å`.
+  pub accents: bool,
+  /// Lengthens the text of each message by this percentage (rounded up), to
+  /// simulate languages that are longer than the source.
+  pub expansion: u16,
+  /// Character used to lengthen the text
+  pub padding: char,
+  /// Wraps each message (or each branch of a selector) in these
+  /// characters, so that truncation is visible.
+  pub brackets: Option<(char, char)>,
+}
+
+impl Default for PseudoOptions {
+  /// Accents, 30% expansion with `~`, and `[...]`
+  fn default() -> Self {
+    Self {
+      accents: true,
+      expansion: 30,
+      padding: '~',
+      brackets: Some(('[', ']')),
+    }
+  }
+}
+
+impl Resolver {
+  /// Creates a pseudo-locale from `self`.
+  ///
+  /// See [crate::pseudo].
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use glossa_dsl::{Resolver, pseudo::PseudoOptions};
+  ///
+  /// let en: Resolver = [
+  ///   ("greeting", "Hello {$name}!"),
+  ///   ("apples", "$num ->\n [1] An apple\n *[other] {$num} apples"),
+  /// ]
+  /// .try_into()?;
+  ///
+  /// let pseudo = en.pseudo_localize(&PseudoOptions::default());
+  ///
+  /// assert_eq!(
+  ///   pseudo.get_with_context("greeting", &[("name", "Tom")])?,
+  ///   "[Ĥéļļö Tom!~~~]"
+  /// );
+  /// assert_eq!(
+  ///   pseudo.get_with_context("apples", &[("num", "2")])?,
+  ///   "[2 åþþļéš~~~]"
+  /// );
+  ///
+  /// # Ok::<(), glossa_dsl::Error>(())
+  /// ```
+  pub fn pseudo_localize(&self, options: &PseudoOptions) -> Resolver {
+    let graph = self.dependency_graph();
+    let inputs = self
+      .values()
+      .flat_map(selector_inputs)
+      .pipe(|keys| graph.reachable_from(keys));
+
+    self
+      .iter()
+      .map(|(key, tmpl)| match inputs.contains(key.as_str()) {
+        true => (key.clone(), tmpl.clone()),
+        _ => (key.clone(), options.transform(tmpl)),
+      })
+      .collect::<AST>()
+      .pipe(Resolver::from)
+      .with_settings_of(self)
+  }
+}
+
+/// Keys of the messages that the selectors of a template are on
+fn selector_inputs(template: &Template) -> Vec<&str> {
+  let Template::Conditional(selector) = template else {
+    return Vec::new();
+  };
+  let own = match selector.input {
+    SelectorInput::Reference => Some(selector.param.as_str()),
+    SelectorInput::Parameter => None,
+  };
+  selector
+    .cases
+    .iter()
+    .map(|(_, tmpl)| tmpl)
+    .chain(selector.default.as_deref())
+    .flat_map(selector_inputs)
+    .chain(own)
+    .collect()
+}
+
+impl PseudoOptions {
+  /// Transforms the text of a template.
+  pub fn transform(&self, template: &Template) -> Template {
+    match template {
+      Template::Parts(parts) => self
+        .transform_parts(parts)
+        .pipe(Template::Parts),
+      Template::Conditional(selector) => Selector {
+        cases: selector
+          .cases
+          .iter()
+          .map(|(case, tmpl)| (case.clone(), self.transform(tmpl)))
+          .collect(),
+        default: selector
+          .default
+          .as_deref()
+          .map(|tmpl| {
+            self
+              .transform(tmpl)
+              .pipe(Box::new)
+          }),
+        ..selector.clone()
+      }
+      .pipe(Template::Conditional),
+    }
+  }
+
+  /// Accents a text.
+  ///
+  /// Neither expansion nor brackets are applied, since they depend on the
+  /// whole message.
+  pub fn accent(&self, text: &str) -> MiniStr {
+    if !self.accents {
+      return text.into();
+    }
+    text
+      .chars()
+      .map(|c| match c {
+        'A'..='Z' => ACCENTED_UPPER[(c as u8 - b'A') as usize],
+        'a'..='z' => ACCENTED_LOWER[(c as u8 - b'a') as usize],
+        _ => c,
+      })
+      .collect()
+  }
+
+  fn transform_parts(&self, parts: &TinyTemplateParts) -> TinyTemplateParts {
+    let mut text_len = 0;
+    let mut new_parts = parts
+      .iter()
+      .map(|part| match part {
+        TemplatePart::Text(text) => {
+          text_len += text.chars().count();
+          self
+            .accent(text)
+            .pipe(TemplatePart::Text)
+        }
+        other => other.clone(),
+      })
+      .collect::<TinyTemplateParts>();
+
+    let padding = (text_len * usize::from(self.expansion)).div_ceil(100);
+    let (open, close) = self
+      .brackets
+      .map_or((None, None), |(open, close)| (Some(open), Some(close)));
+
+    // Keeps adjacent text merged, as the parser does.
+    match new_parts.first_mut() {
+      Some(TemplatePart::Text(text)) => {
+        if let Some(open) = open {
+          text.insert(0, open)
+        }
+      }
+      _ => {
+        if let Some(open) = open {
+          new_parts.insert(0, TemplatePart::Text(open.to_compact_string()))
+        }
+      }
+    }
+
+    let suffix = core::iter::repeat_n(self.padding, padding)
+      .chain(close)
+      .collect::<MiniStr>();
+    if suffix.is_empty() {
+      return new_parts;
+    }
+    match new_parts.last_mut() {
+      Some(TemplatePart::Text(text)) => text.push_str(&suffix),
+      _ => new_parts.push(TemplatePart::Text(suffix)),
+    }
+    new_parts
+  }
+}
@@ -0,0 +1,165 @@
+//! Hot reloading of translation files for development.
+//!
+//! [ReloadableResolver] polls the modification times of its source files.
+//! When a file changes, all files are parsed and validated again, and the
+//! new messages replace the old ones only if there is no error.
+use std::{
+  fs,
+  path::{Path, PathBuf},
+  time::SystemTime,
+};
+
+use tap::Pipe;
+
+use crate::{Resolver, error::ResolverResult, loader::load_files};
+
+/// Result of [ReloadableResolver::poll]
+#[derive(Debug)]
+pub enum ReloadStatus {
+  /// No source file was modified.
+  Unchanged,
+  /// The messages were replaced.
+  Reloaded,
+  /// The modified files contain errors; the previous messages are kept.
+  Failed(crate::Error),
+}
+
+/// A [Resolver] that reloads its source files on change.
+///
+/// ## Example
+///
+/// ```no_run
+/// use glossa_dsl::reload::{ReloadStatus, ReloadableResolver};
+///
+/// let mut res = ReloadableResolver::new(["locales/en/main.toml"])?;
+///
+/// loop {
+///   if let ReloadStatus::Failed(e) = res.poll() {
+///     eprintln!("Failed to reload translations: {e}");
+///   }
+///
+///   let _text = res.get_with_context("greeting", &[("name", "Tom")]);
+///   # break;
+/// }
+///
+/// # Ok::<(), glossa_dsl::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct ReloadableResolver {
+  resolver: Resolver,
+  files: Vec<PathBuf>,
+  modified: Vec<Option<SystemTime>>,
+}
+
+impl core::ops::Deref for ReloadableResolver {
+  type Target = Resolver;
+
+  fn deref(&self) -> &Self::Target {
+    &self.resolver
+  }
+}
+
+impl ReloadableResolver {
+  /// Loads the files (see [load_files]).
+  ///
+  /// Unlike [Self::poll], it fails if the files contain errors.
+  pub fn new<P, I>(files: I) -> ResolverResult<Self>
+  where
+    P: Into<PathBuf>,
+    I: IntoIterator<Item = P>,
+  {
+    let files = files
+      .into_iter()
+      .map(Into::into)
+      .collect::<Vec<_>>();
+    let modified = files
+      .iter()
+      .map(modified_time)
+      .collect();
+
+    Self {
+      resolver: load_validated(&files)?,
+      files,
+      modified,
+    }
+    .pipe(Ok)
+  }
+
+  /// Returns the current messages.
+  pub fn resolver(&self) -> &Resolver {
+    &self.resolver
+  }
+
+  /// Sets the [BidiIsolation](crate::resolver::BidiIsolation) of the getters
+  /// without options, see [Resolver::set_bidi_isolation].
+  pub fn set_bidi_isolation(&mut self, isolation: crate::resolver::BidiIsolation) {
+    self
+      .resolver
+      .set_bidi_isolation(isolation)
+  }
+
+  /// Sets the [MissingKeyPolicy](crate::resolver::MissingKeyPolicy) of the
+  /// getters without options, see [Resolver::set_missing_key_policy].
+  pub fn set_missing_key_policy(
+    &mut self,
+    policy: crate::resolver::MissingKeyPolicy<'static>,
+  ) {
+    self
+      .resolver
+      .set_missing_key_policy(policy)
+  }
+
+  /// Returns the watched files.
+  pub fn files(&self) -> &[PathBuf] {
+    &self.files
+  }
+
+  /// Reloads the files if any of them was modified since the last poll.
+  ///
+  /// A failed reload is not retried until a file is modified again.
+  pub fn poll(&mut self) -> ReloadStatus {
+    let modified = self
+      .files
+      .iter()
+      .map(modified_time)
+      .collect::<Vec<_>>();
+
+    if modified == self.modified {
+      return ReloadStatus::Unchanged;
+    }
+    self.modified = modified;
+
+    match self.reload() {
+      Ok(()) => ReloadStatus::Reloaded,
+      Err(e) => ReloadStatus::Failed(e),
+    }
+  }
+
+  /// Reloads the files now.
+  ///
+  /// On error, the current messages are kept. The settings of the current
+  /// resolver (e.g., [Resolver::bidi_isolation]) are kept in any case.
+  pub fn reload(&mut self) -> ResolverResult<()> {
+    self.resolver = load_validated(&self.files)?.with_settings_of(&self.resolver);
+    Ok(())
+  }
+
+  /// Takes ownership of the Self and returns the current messages.
+  pub fn into_inner(self) -> Resolver {
+    self.resolver
+  }
+}
+
+fn load_validated(files: &[PathBuf]) -> ResolverResult<Resolver> {
+  let resolver = load_files(files)?;
+  resolver.validate()?;
+  Ok(resolver)
+}
+
+/// A missing file has no modification time, so that deleting and
+/// re-creating it is also detected.
+fn modified_time<P: AsRef<Path>>(file: P) -> Option<SystemTime> {
+  fs::metadata(file)
+    .and_then(|m| m.modified())
+    .ok()
+}
@@ -5,13 +5,26 @@ mod bin_code;
 #[cfg(feature = "bincode")]
 pub(crate) mod bin_code_nostd;
 
+mod consistency;
 mod from_slice;
+mod graph;
 mod lookup_value;
+mod merge;
+mod options;
 mod ordered_map;
+mod segments;
+mod tree;
+mod update;
+pub(crate) mod validate;
 
 #[cfg(feature = "std")]
 mod std_impl;
 
+#[cfg(feature = "shared")]
+mod shared;
+#[cfg(feature = "shared")]
+pub use shared::SharedResolver;
+
 use alloc::collections::BTreeMap;
 
 /// Compact string type optimized for small string storage.
@@ -24,6 +37,16 @@ pub use kstring::KString;
 
 use crate::template::Template;
 
+pub use consistency::{ConsistencyReport, ParameterMismatch};
+pub use graph::DependencyGraph;
+pub use merge::{MergePolicy, MergeReport};
+pub use options::{
+  BidiIsolation, EscapeMode, MissingKeyPolicy, MissingParameterPolicy,
+  ResolveOptions, Resolved, is_rtl_locale,
+};
+pub use segments::Segment;
+pub use tree::Node;
+
 #[cfg(feature = "std")]
 pub type AHashRawMap = ahash::HashMap<KString, MiniStr>;
 
@@ -49,14 +72,77 @@ pub type AST = OrderedAST;
 ///   - Uses HashMap with std for O(1) lookups
 /// - no_std:
 ///   - Falls back to BTreeMap in no_std (O(log n) lookups)
-#[derive(Default, Debug, Clone, PartialEq)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct Resolver(pub AST);
+///
+/// ## Settings
+///
+/// Besides the messages, a resolver has settings of the getters without
+/// options, e.g., [Resolver::set_bidi_isolation]. They are neither
+/// serialized nor compared: a decoded resolver has the default settings, and
+/// two resolvers with the same messages are equal.
+///
+/// Since 0.0.17, the fields are private. Use [Resolver::new] (or
+/// [From]`<AST>`) instead of `Resolver(ast)`, and [Resolver::into_inner]
+/// instead of `let Resolver(ast) = res`.
+#[derive(Default, Debug, Clone)]
+#[cfg_attr(
+  feature = "serde",
+  derive(serde::Serialize, serde::Deserialize),
+  serde(transparent)
+)]
+pub struct Resolver {
+  ast: AST,
+  #[cfg_attr(feature = "serde", serde(skip))]
+  bidi_isolation: BidiIsolation,
+  #[cfg_attr(feature = "serde", serde(skip))]
+  missing_key: MissingKeyPolicy<'static>,
+}
+
+impl Resolver {
+  /// Creates a resolver with the default settings.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use glossa_dsl::{Resolver, resolver::AST};
+  ///
+  /// let res = Resolver::new(AST::default());
+  /// assert!(res.is_empty());
+  /// ```
+  pub fn new(ast: AST) -> Self {
+    Self {
+      ast,
+      bidi_isolation: BidiIsolation::Off,
+      missing_key: MissingKeyPolicy::Error,
+    }
+  }
+
+  /// Replaces the settings with those of `other`, and keeps the messages.
+  pub(crate) fn with_settings_of(self, other: &Self) -> Self {
+    Self {
+      bidi_isolation: other.bidi_isolation,
+      missing_key: other.missing_key,
+      ..self
+    }
+  }
+}
+
+impl From<AST> for Resolver {
+  fn from(ast: AST) -> Self {
+    Self::new(ast)
+  }
+}
+
+/// Compares the messages only, not the settings.
+impl PartialEq for Resolver {
+  fn eq(&self, other: &Self) -> bool {
+    self.ast == other.ast
+  }
+}
 
 impl core::ops::Deref for Resolver {
   type Target = AST;
 
   fn deref(&self) -> &Self::Target {
-    &self.0
+    &self.ast
   }
 }
@@ -50,6 +50,14 @@ impl Resolver {
   ///
   /// It Uses buffered reading for I/O efficiency.
   ///
+  /// ## Compatibility
+  ///
+  /// bincode is not self-describing, so a bundle can only be decoded by the
+  /// version of this crate that encoded it. Since 0.0.17, the AST has
+  /// translator notes, term selectors and other new fields: bundles encoded
+  /// by 0.0.16 or earlier fail to decode, and must be compiled again from
+  /// the source files.
+  ///
   /// ## Example
   ///
   /// ```no_run
@@ -16,6 +16,9 @@ impl Resolver {
   /// - A tuple of (deserialized Resolver, amount of bytes read) on success
   /// - Error details if deserialization fails
   ///
+  /// Bundles encoded by 0.0.16 or earlier cannot be decoded, see
+  /// [Resolver::decode_bin](crate::Resolver::decode_bin).
+  ///
   /// See also: [bincode::serde::decode_from_slice]
   pub fn decode_bin_from_slice(slice: &[u8]) -> ResolverResult<(Self, usize)> {
     bincode::serde::decode_from_slice(slice, bincode_std_cfg())?.pipe(Ok)
@@ -0,0 +1,131 @@
+use alloc::vec::Vec;
+
+use crate::resolver::Resolver;
+
+/// Result of [Resolver::check_consistency]
+///
+/// All lists are in key order.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ConsistencyReport<'a> {
+  /// Keys of the source that the translation lacks
+  pub missing: Vec<&'a str>,
+  /// Keys of the translation that the source lacks
+  pub extra: Vec<&'a str>,
+  /// Messages whose parameters differ between the source and the translation
+  pub mismatched: Vec<ParameterMismatch<'a>>,
+}
+
+/// Parameter differences of a message in [ConsistencyReport]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ParameterMismatch<'a> {
+  pub key: &'a str,
+  /// Parameters used by the source, but not by the translation
+  pub missing: Vec<&'a str>,
+  /// Parameters used by the translation, but not by the source
+  pub extra: Vec<&'a str>,
+}
+
+impl ConsistencyReport<'_> {
+  /// Returns true if the translation has exactly the keys of the source, and
+  /// every message uses the same parameters.
+  pub fn is_consistent(&self) -> bool {
+    self.missing.is_empty() && self.extra.is_empty() && self.mismatched.is_empty()
+  }
+}
+
+impl Resolver {
+  /// Compares a translation with `self` (the source, e.g., English).
+  ///
+  /// It reports keys that are missing or extra in the translation, and
+  /// messages whose sets of `$parameters` differ (see
+  /// [Template::parameters](crate::Template::parameters)).
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use glossa_dsl::{
+  ///   Resolver,
+  ///   resolver::{ConsistencyReport, ParameterMismatch},
+  /// };
+  ///
+  /// let en: Resolver = [
+  ///   ("apples", "$num ->\n [1] An apple\n *[other] {$num} apples"),
+  ///   ("greeting", "Hello {$name}"),
+  ///   ("bye", "Goodbye"),
+  /// ]
+  /// .try_into()?;
+  ///
+  /// let zh: Resolver = [
+  ///   ("apples", "一些苹果"),
+  ///   ("greeting", "你好 {$name}"),
+  ///   ("thanks", "谢谢"),
+  /// ]
+  /// .try_into()?;
+  ///
+  /// let report = en.check_consistency(&zh);
+  /// assert!(!report.is_consistent());
+  /// assert_eq!(
+  ///   report,
+  ///   ConsistencyReport {
+  ///     missing: vec!["bye"],
+  ///     extra: vec!["thanks"],
+  ///     mismatched: vec![ParameterMismatch {
+  ///       key: "apples",
+  ///       missing: vec!["num"],
+  ///       extra: vec![],
+  ///     }],
+  ///   }
+  /// );
+  ///
+  /// # Ok::<(), glossa_dsl::Error>(())
+  /// ```
+  pub fn check_consistency<'a>(
+    &'a self,
+    translation: &'a Resolver,
+  ) -> ConsistencyReport<'a> {
+    let mut report = ConsistencyReport::default();
+
+    for (key, tmpl) in self
+      .iter()
+      .map(|(k, v)| (k.as_str(), v))
+    {
+      let Some(translated) = translation.get(key) else {
+        report.missing.push(key);
+        continue;
+      };
+
+      let (expected, actual) = (tmpl.parameters(), translated.parameters());
+      let difference = |a: &[&'a str], b: &[&'a str]| {
+        let mut diff = a
+          .iter()
+          .filter(|p| !b.contains(p))
+          .copied()
+          .collect::<Vec<_>>();
+        diff.sort_unstable();
+        diff
+      };
+
+      let mismatch = ParameterMismatch {
+        key,
+        missing: difference(&expected, &actual),
+        extra: difference(&actual, &expected),
+      };
+      if !mismatch.missing.is_empty() || !mismatch.extra.is_empty() {
+        report.mismatched.push(mismatch)
+      }
+    }
+
+    report.extra = translation
+      .keys()
+      .map(|key| key.as_str())
+      .filter(|key| !self.contains_key(*key))
+      .collect();
+
+    report.missing.sort_unstable();
+    report.extra.sort_unstable();
+    report
+      .mismatched
+      .sort_unstable_by_key(|m| m.key);
+    report
+  }
+}
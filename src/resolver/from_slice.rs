@@ -117,18 +117,18 @@ impl Resolver {
           .map(|tmpl| (convert_map_key(key.as_ref()), tmpl))
       })
       .collect::<Result<AST, _>>()?
-      .pipe(Self)
+      .pipe(Self::from)
       .pipe(Ok)
   }
 }
 
 #[cfg(not(feature = "std"))]
-fn convert_map_key(key: &str) -> crate::MiniStr {
+pub(super) fn convert_map_key(key: &str) -> crate::MiniStr {
   key.into()
 }
 
 #[cfg(feature = "std")]
-fn convert_map_key(key: &str) -> kstring::KString {
+pub(super) fn convert_map_key(key: &str) -> kstring::KString {
   key.pipe(kstring::KString::from_ref)
 }
 
@@ -0,0 +1,315 @@
+use alloc::{
+  collections::{BTreeMap, BTreeSet},
+  vec::Vec,
+};
+
+use tap::Pipe;
+
+use crate::{
+  error::ResolverResult,
+  resolver::{Resolver, validate::cyclic_reference},
+};
+
+type Edges<'a> = BTreeMap<&'a str, Vec<&'a str>>;
+
+/// Which messages reference which, derived from `{ ref }` placeables
+/// (including selector branches and parameterised references).
+///
+/// Keys are sorted, so every result is deterministic.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DependencyGraph<'a> {
+  /// message => messages it references
+  forward: Edges<'a>,
+  /// message => messages that reference it
+  reverse: Edges<'a>,
+}
+
+impl Resolver {
+  /// Builds the dependency graph of all messages.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use glossa_dsl::Resolver;
+  ///
+  /// let res: Resolver = [
+  ///   ("g", "Good"),
+  ///   ("time-period", "$period ->\n [morning] {g} Morning\n *[other] {g} {$period}"),
+  ///   ("gender", "$attr ->\n [male] Mr.\n *[female] Ms."),
+  ///   ("greeting", "{ time-period }! { gender }{ $name }"),
+  /// ]
+  /// .try_into()?;
+  ///
+  /// let graph = res.dependency_graph();
+  /// assert_eq!(graph.dependencies("greeting"), ["time-period", "gender"]);
+  /// assert_eq!(graph.dependents("g"), ["time-period"]);
+  ///
+  /// assert_eq!(
+  ///   graph.topological_order()?,
+  ///   ["g", "gender", "time-period", "greeting"]
+  /// );
+  ///
+  /// # Ok::<(), glossa_dsl::Error>(())
+  /// ```
+  pub fn dependency_graph(&self) -> DependencyGraph<'_> {
+    let forward = self
+      .iter()
+      .map(|(key, tmpl)| (key.as_str(), tmpl.references()))
+      .collect::<Edges>();
+
+    let reverse = forward
+      .iter()
+      .flat_map(|(&key, refs)| {
+        refs
+          .iter()
+          .map(move |&r| (r, key))
+      })
+      .fold(Edges::new(), |mut reverse, (r, key)| {
+        reverse
+          .entry(r)
+          .or_default()
+          .push(key);
+        reverse
+      });
+
+    DependencyGraph { forward, reverse }
+  }
+
+  /// Returns the sorted keys of messages that cannot be reached from
+  /// `entries`, neither directly nor through `{ ref }` placeables.
+  ///
+  /// `entries` are the keys used by the application code; the result is the
+  /// list of dead messages that can be removed.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use glossa_dsl::Resolver;
+  ///
+  /// let res: Resolver = [
+  ///   ("app-name", "Glossa"),
+  ///   ("title", "{ app-name } Settings"),
+  ///   ("old-title", "{ app-name } Preferences"),
+  ///   ("legacy", "Deprecated"),
+  /// ]
+  /// .try_into()?;
+  ///
+  /// assert_eq!(res.unused_keys(["title"]), ["legacy", "old-title"]);
+  ///
+  /// # Ok::<(), glossa_dsl::Error>(())
+  /// ```
+  pub fn unused_keys<'k, I>(&self, entries: I) -> Vec<&str>
+  where
+    I: IntoIterator<Item = &'k str>,
+  {
+    let graph = self.dependency_graph();
+    let used = graph.reachable_from(entries);
+
+    graph
+      .keys()
+      .filter(|k| !used.contains(k))
+      .collect()
+  }
+}
+
+impl<'a> DependencyGraph<'a> {
+  /// Keys of all messages
+  pub fn keys(&self) -> impl Iterator<Item = &'a str> + '_ {
+    self.forward.keys().copied()
+  }
+
+  /// Messages that `key` references directly, in source order.
+  ///
+  /// They may include undefined messages.
+  pub fn dependencies(&self, key: &str) -> &[&'a str] {
+    self
+      .forward
+      .get(key)
+      .map_or(&[], |v| v.as_slice())
+  }
+
+  /// Messages that reference `key` directly, in key order.
+  pub fn dependents(&self, key: &str) -> &[&'a str] {
+    self
+      .reverse
+      .get(key)
+      .map_or(&[], |v| v.as_slice())
+  }
+
+  /// Every message transitively referenced by `keys`, including `keys`
+  /// themselves.
+  pub fn reachable_from<'k, I>(&self, keys: I) -> BTreeSet<&'a str>
+  where
+    I: IntoIterator<Item = &'k str>,
+  {
+    self.walk(&self.forward, keys)
+  }
+
+  /// Every message that transitively references one of `keys`, including
+  /// `keys` themselves.
+  ///
+  /// It answers "which messages are affected if these messages change?".
+  pub fn affected_by<'k, I>(&self, keys: I) -> BTreeSet<&'a str>
+  where
+    I: IntoIterator<Item = &'k str>,
+  {
+    self.walk(&self.reverse, keys)
+  }
+
+  fn walk<'k, I>(&self, edges: &Edges<'a>, keys: I) -> BTreeSet<&'a str>
+  where
+    I: IntoIterator<Item = &'k str>,
+  {
+    // Only existing messages (and keys that have edges) can be borrowed
+    // with the lifetime of the graph.
+    let mut stack = keys
+      .into_iter()
+      .filter_map(|k| self.intern(k))
+      .collect::<Vec<_>>();
+    let mut visited = BTreeSet::new();
+
+    while let Some(key) = stack.pop() {
+      if visited.insert(key) {
+        edges
+          .get(key)
+          .into_iter()
+          .flatten()
+          .for_each(|&next| stack.push(next));
+      }
+    }
+    visited
+  }
+
+  fn intern(&self, key: &str) -> Option<&'a str> {
+    self
+      .forward
+      .get_key_value(key)
+      .or_else(|| self.reverse.get_key_value(key))
+      .map(|(&k, _)| k)
+  }
+
+  /// Sorts the messages so that every message comes after the messages it
+  /// references.
+  ///
+  /// Undefined references are ignored. Fails with
+  /// [CyclicReference](crate::Error::CyclicReference) if there is a cycle.
+  pub fn topological_order(&self) -> ResolverResult<Vec<&'a str>> {
+    let mut done = BTreeSet::new();
+    let mut path = Vec::new();
+    let mut order = Vec::with_capacity(self.forward.len());
+
+    for key in self.keys() {
+      self.visit(key, &mut done, &mut path, &mut order)?;
+    }
+    Ok(order)
+  }
+
+  fn visit(
+    &self,
+    key: &'a str,
+    done: &mut BTreeSet<&'a str>,
+    path: &mut Vec<&'a str>,
+    order: &mut Vec<&'a str>,
+  ) -> ResolverResult<()> {
+    if done.contains(key) || !self.forward.contains_key(key) {
+      return Ok(());
+    }
+
+    if path.contains(&key) {
+      return cyclic_reference(path, key).pipe(Err);
+    }
+
+    path.push(key);
+    for &dep in self.dependencies(key) {
+      self.visit(dep, done, path, order)?;
+    }
+    path.pop();
+
+    done.insert(key);
+    order.push(key);
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::error::ResolverError;
+
+  #[test]
+  fn test_reachability() -> ResolverResult<()> {
+    let res: Resolver = [
+      ("a", "{ b } { c(x: $y) }"),
+      ("b", "{ d }"),
+      ("c", "$x ->\n [1] { d }\n *[other] { e }"),
+      ("d", "D"),
+      ("unused", "{ d }"),
+    ]
+    .try_into()?;
+    let graph = res.dependency_graph();
+
+    let reachable = graph.reachable_from(["a"]);
+    assert_eq!(
+      reachable
+        .into_iter()
+        .collect::<Vec<_>>(),
+      ["a", "b", "c", "d", "e"]
+    );
+
+    let affected = graph.affected_by(["d"]);
+    assert_eq!(
+      affected
+        .into_iter()
+        .collect::<Vec<_>>(),
+      ["a", "b", "c", "d", "unused"]
+    );
+
+    assert!(
+      graph
+        .reachable_from(["missing"])
+        .is_empty()
+    );
+
+    let mut cyclic = res.clone();
+    cyclic.insert_source("d", "{ a }")?;
+    assert!(matches!(
+      cyclic.dependency_graph().topological_order(),
+      Err(ResolverError::CyclicReference(path)) if path == "a -> b -> d -> a"
+    ));
+    Ok(())
+  }
+
+  #[test]
+  fn test_unused_keys() -> ResolverResult<()> {
+    let res: Resolver = [
+      ("title", "{ app-name } { menu }"),
+      ("app-name", "Glossa"),
+      ("menu", "$n ->\n [0] { empty }\n *[other] { items(n: $n) }"),
+      ("empty", "Empty"),
+      ("items", "{$n} items"),
+      ("old-a", "{ old-b }"),
+      ("old-b", "{ old-a } { app-name }"),
+      ("legacy", "Deprecated"),
+    ]
+    .try_into()?;
+
+    // Transitive references and references inside selector branches
+    assert_eq!(res.unused_keys(["title"]), ["legacy", "old-a", "old-b"]);
+
+    // Entry keys that do not exist are ignored.
+    assert_eq!(
+      res.unused_keys(["title", "missing"]),
+      ["legacy", "old-a", "old-b"]
+    );
+    assert_eq!(
+      res
+        .unused_keys(["missing"])
+        .len(),
+      res.len()
+    );
+
+    // A cycle is used if any of its messages is used.
+    assert_eq!(res.unused_keys(["title", "old-b"]), ["legacy"]);
+    Ok(())
+  }
+}
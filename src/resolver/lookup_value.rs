@@ -1,3 +1,5 @@
+use alloc::vec::Vec;
+
 use tap::{Pipe, Tap};
 use tinyvec::TinyVec;
 
@@ -5,9 +7,8 @@ use tinyvec::TinyVec;
 use crate::{
   MiniStr,
   error::{ResolverError, ResolverResult},
-  parsers::context::Context,
-  resolver::{BTreeRawMap, Resolver},
-  template::Template,
+  parsers::{context::Context, process_tmpl::Processor},
+  resolver::{BTreeRawMap, ResolveOptions, Resolved, Resolver},
 };
 
 impl Resolver {
@@ -61,6 +62,131 @@ impl Resolver {
     .pipe_ref(process)
   }
 
+  /// Similar to [Self::get_with_context], but with [ResolveOptions].
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use glossa_dsl::{
+  ///   Resolver,
+  ///   resolver::{MissingKeyPolicy, MissingParameterPolicy, ResolveOptions},
+  /// };
+  ///
+  /// let res: Resolver = [
+  ///   ("greeting", "Hello {$name}, it is {$time}"),
+  ///   ("title", "{ app-name } Settings"),
+  /// ]
+  /// .try_into()?;
+  ///
+  /// let ctx = [("name", "Tom")];
+  /// assert!(res.get_with_context("greeting", &ctx).is_err());
+  ///
+  /// let options = ResolveOptions {
+  ///   missing_parameter: MissingParameterPolicy::Literal,
+  ///   ..Default::default()
+  /// };
+  /// let resolved = res.resolve("greeting", &ctx, &options)?;
+  /// assert_eq!(resolved.text, "Hello Tom, it is {$time}");
+  /// assert_eq!(resolved.missing_parameters, ["time"]);
+  ///
+  /// let unknown = |name: &str| format!("<{name}?>").into();
+  /// let options = ResolveOptions {
+  ///   missing_parameter: MissingParameterPolicy::Callback(&unknown),
+  ///   ..Default::default()
+  /// };
+  /// let resolved = res.resolve("greeting", &ctx, &options)?;
+  /// assert_eq!(resolved.text, "Hello Tom, it is <time?>");
+  ///
+  /// let en: Resolver = [("app-name", "Glossa")].try_into()?;
+  ///
+  /// let options = ResolveOptions {
+  ///   missing_key: MissingKeyPolicy::Placeholder,
+  ///   ..Default::default()
+  /// };
+  /// let resolved = res.resolve("title", &[], &options)?;
+  /// assert_eq!(resolved.text, "⟦app-name⟧ Settings");
+  /// assert_eq!(resolved.missing_keys, ["app-name"]);
+  ///
+  /// let options = ResolveOptions {
+  ///   missing_key: MissingKeyPolicy::Fallback(&en),
+  ///   ..Default::default()
+  /// };
+  /// assert_eq!(res.resolve("title", &[], &options)?.text, "Glossa Settings");
+  ///
+  /// # Ok::<(), glossa_dsl::Error>(())
+  /// ```
+  pub fn resolve(
+    &self,
+    var_name: &str,
+    context: &[(&str, &str)],
+    options: &ResolveOptions<'_>,
+  ) -> ResolverResult<Resolved> {
+    with_slice_context(context, |ctx| {
+      let processor = Processor::new(self, options);
+      let mut text = MiniStr::const_new("");
+      processor.try_get_template_and_process(var_name, ctx, &mut text)?;
+      Ok(processor.into_resolved(text))
+    })
+  }
+
+  /// Similar to [Self::resolve], but never fails.
+  ///
+  /// Instead of aborting on the first error, it renders a substitute and
+  /// collects the error:
+  ///
+  /// - [ResolverError::MissingParameter] => `{$name}`
+  /// - [ResolverError::UndefinedVariable] => `{key}`
+  /// - [ResolverError::CyclicReference] => `{key}`
+  /// - [ResolverError::NoDefaultBranch] => nothing
+  ///
+  /// Parameters and keys handled by a policy other than `Error` are
+  /// reported in [Resolved] as usual.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use glossa_dsl::{Error, Resolver};
+  ///
+  /// let res: Resolver = [
+  ///   ("greeting", "{ hello }, {$name}! { time-period }"),
+  ///   ("time-period", "$period ->\n [morning] Good Morning"),
+  /// ]
+  /// .try_into()?;
+  ///
+  /// let ctx = [("period", "evening")];
+  /// let (resolved, errors) = res.resolve_lossy("greeting", &ctx, &Default::default());
+  ///
+  /// assert_eq!(resolved.text, "{hello}, {$name}! ");
+  /// assert!(matches!(
+  ///   errors.as_slice(),
+  ///   [
+  ///     Error::UndefinedVariable(_),
+  ///     Error::MissingParameter(_),
+  ///     Error::NoDefaultBranch(_),
+  ///   ]
+  /// ));
+  ///
+  /// # Ok::<(), glossa_dsl::Error>(())
+  /// ```
+  pub fn resolve_lossy(
+    &self,
+    var_name: &str,
+    context: &[(&str, &str)],
+    options: &ResolveOptions<'_>,
+  ) -> (Resolved, Vec<ResolverError>) {
+    with_slice_context(context, |ctx| {
+      let processor = Processor::new(self, options).lossy();
+      let mut text = MiniStr::const_new("");
+      let result = processor.try_get_template_and_process(var_name, ctx, &mut text);
+
+      let (resolved, mut errors) = processor.into_lossy_resolved(text);
+      if let Err(e) = result {
+        errors.push(e)
+      }
+      (resolved, errors)
+    })
+  }
+
   /// Similar to [Self::get_with_context], but the context is
   /// `BTreeMap<MiniStr, MiniStr>` instead of `&[(&str, &str)]`.
   pub fn get_with_ctx_btree_map(
@@ -187,13 +313,24 @@ impl Resolver {
     }
     .pipe_ref(process)
   }
+}
 
-  pub(crate) fn try_get_template(&self, key: &str) -> ResolverResult<&Template> {
-    self
-      .0
-      .get(key)
-      .ok_or_else(|| ResolverError::UndefinedVariable(key.into()))
+/// Sorts the context for binary search, then calls `f`.
+pub(super) fn with_slice_context<T>(
+  context: &[(&str, &str)],
+  f: impl FnOnce(&Context<'_>) -> T,
+) -> T {
+  match context.is_empty() {
+    true => return f(&Context::Empty),
+    _ => context
+      .iter()
+      .copied()
+      .collect::<TinyVec<[(&str, &str); 5]>>()
+      .tap_mut(|x| x.sort_unstable_by_key(|&(k, _)| k)),
   }
+  .as_ref()
+  .pipe(Context::Slice)
+  .pipe_ref(f)
 }
 
 #[cfg(test)]
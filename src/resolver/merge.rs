@@ -0,0 +1,135 @@
+use alloc::vec::Vec;
+
+use tap::Pipe;
+
+use crate::{
+  MiniStr,
+  error::{ResolverError, ResolverResult},
+  resolver::Resolver,
+};
+
+/// How [Resolver::merge] handles a key that exists in both resolvers
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum MergePolicy {
+  /// Keeps the existing message, and ignores the new one.
+  KeepFirst,
+  /// Replaces the existing message with the new one.
+  KeepLast,
+  /// Fails with [ResolverError::ConflictingKey], without modifying the
+  /// resolver.
+  #[default]
+  ErrorOnConflict,
+}
+
+/// Result of [Resolver::merge]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct MergeReport {
+  /// Keys whose existing message was replaced ([MergePolicy::KeepLast]),
+  /// in key order
+  pub overridden: Vec<MiniStr>,
+  /// Keys whose new message was ignored ([MergePolicy::KeepFirst]), in key
+  /// order
+  pub ignored: Vec<MiniStr>,
+}
+
+impl Resolver {
+  /// Merges the messages of `other` into `self`.
+  ///
+  /// It is useful to layer product-specific overrides onto a base
+  /// translation set.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use glossa_dsl::{
+  ///   Resolver,
+  ///   resolver::{MergePolicy, MergeReport},
+  /// };
+  ///
+  /// let mut base: Resolver = [
+  ///   ("app", "Glossa"),
+  ///   ("greeting", "Welcome to { app }"),
+  /// ]
+  /// .try_into()?;
+  ///
+  /// let overrides: Resolver = [("app", "Glossa Pro")].try_into()?;
+  ///
+  /// assert!(base.clone().merge(overrides.clone(), MergePolicy::ErrorOnConflict).is_err());
+  ///
+  /// let report = base.merge(overrides, MergePolicy::KeepLast)?;
+  /// assert_eq!(report.overridden, ["app"]);
+  /// assert_eq!(base.try_get("greeting")?, "Welcome to Glossa Pro");
+  ///
+  /// # Ok::<(), glossa_dsl::Error>(())
+  /// ```
+  pub fn merge(
+    &mut self,
+    other: Resolver,
+    policy: MergePolicy,
+  ) -> ResolverResult<MergeReport> {
+    let mut conflicts = other
+      .keys()
+      .filter(|k| self.contains_key(k.as_str()))
+      .map(|k| MiniStr::from(k.as_str()))
+      .collect::<Vec<_>>();
+    conflicts.sort_unstable();
+
+    if let (MergePolicy::ErrorOnConflict, Some(key)) = (policy, conflicts.first()) {
+      return key
+        .clone()
+        .pipe(ResolverError::ConflictingKey)
+        .pipe(Err);
+    }
+
+    let keep_first = policy == MergePolicy::KeepFirst;
+    for (key, tmpl) in other.into_inner() {
+      if keep_first && self.contains_key(key.as_str()) {
+        continue;
+      }
+      self.ast.insert(key, tmpl);
+    }
+
+    match keep_first {
+      true => MergeReport {
+        ignored: conflicts,
+        ..Default::default()
+      },
+      _ => MergeReport {
+        overridden: conflicts,
+        ..Default::default()
+      },
+    }
+    .pipe(Ok)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_merge_policies() -> ResolverResult<()> {
+    let base: Resolver = [("a", "1"), ("b", "2")].try_into()?;
+    let other: Resolver = [("b", "two"), ("c", "three")].try_into()?;
+
+    let mut res = base.clone();
+    let report = res.merge(other.clone(), MergePolicy::KeepFirst)?;
+    assert_eq!(report.ignored, ["b"]);
+    assert!(report.overridden.is_empty());
+    assert_eq!(res.try_get("b")?, "2");
+    assert_eq!(res.try_get("c")?, "three");
+
+    let mut res = base.clone();
+    let report = res.merge(other.clone(), MergePolicy::KeepLast)?;
+    assert_eq!(report.overridden, ["b"]);
+    assert_eq!(res.try_get("b")?, "two");
+
+    let mut res = base.clone();
+    let err = res
+      .merge(other, MergePolicy::ErrorOnConflict)
+      .unwrap_err();
+    assert!(matches!(err, ResolverError::ConflictingKey(k) if k == "b"));
+    assert_eq!(res, base);
+    Ok(())
+  }
+}
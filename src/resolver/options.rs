@@ -0,0 +1,295 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{MiniStr, Resolver, escape::Escaper};
+
+/// Options of [Resolver::resolve]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ResolveOptions<'a> {
+  /// What to render when a `$parameter` is not in the context
+  pub missing_parameter: MissingParameterPolicy<'a>,
+  /// What to render when a message key (including a nested `{ ref }`) is
+  /// not defined
+  pub missing_key: MissingKeyPolicy<'a>,
+  /// Which rendered values are wrapped in Unicode isolation marks
+  pub bidi_isolation: BidiIsolation,
+  /// Which rendered values are escaped, e.g., for HTML
+  pub escape: EscapeMode<'a>,
+}
+
+impl ResolveOptions<'_> {
+  /// Default options for a locale, e.g., `ar` or `he-IL`.
+  ///
+  /// [BidiIsolation::Parameters] is enabled for right-to-left locales (see
+  /// [is_rtl_locale]).
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use glossa_dsl::{Resolver, resolver::ResolveOptions};
+  ///
+  /// let ar: Resolver = [("greeting", "مرحبا {$name}!")].try_into()?;
+  ///
+  /// let options = ResolveOptions::for_locale("ar");
+  /// let resolved = ar.resolve("greeting", &[("name", "Tom")], &options)?;
+  /// assert_eq!(resolved.text, "مرحبا \u{2068}Tom\u{2069}!");
+  ///
+  /// # Ok::<(), glossa_dsl::Error>(())
+  /// ```
+  pub fn for_locale(locale: &str) -> Self {
+    Self {
+      bidi_isolation: BidiIsolation::for_locale(locale),
+      ..Default::default()
+    }
+  }
+}
+
+impl Resolver {
+  /// Which values are wrapped in Unicode isolation marks by the getters
+  /// without options, e.g., [Self::get_with_context].
+  pub const fn bidi_isolation(&self) -> BidiIsolation {
+    self.bidi_isolation
+  }
+
+  /// Sets the [BidiIsolation] of the getters without options.
+  ///
+  /// [Self::resolve] and the other methods that take [ResolveOptions] use
+  /// [ResolveOptions::bidi_isolation] instead.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use glossa_dsl::{Resolver, resolver::BidiIsolation};
+  ///
+  /// let mut ar: Resolver = [("greeting", "مرحبا {$name}!")].try_into()?;
+  /// ar.set_bidi_isolation(BidiIsolation::for_locale("ar"));
+  ///
+  /// let text = ar.get_with_context("greeting", &[("name", "Tom")])?;
+  /// assert_eq!(text, "مرحبا \u{2068}Tom\u{2069}!");
+  ///
+  /// # Ok::<(), glossa_dsl::Error>(())
+  /// ```
+  pub fn set_bidi_isolation(&mut self, isolation: BidiIsolation) {
+    self.bidi_isolation = isolation
+  }
+
+  /// Similar to [Self::set_bidi_isolation], but takes ownership of the Self.
+  pub fn with_bidi_isolation(self, isolation: BidiIsolation) -> Self {
+    Self {
+      bidi_isolation: isolation,
+      ..self
+    }
+  }
+
+  /// How the getters without options handle a key that is not defined,
+  /// e.g., [Self::try_get].
+  pub const fn missing_key_policy(&self) -> MissingKeyPolicy<'static> {
+    self.missing_key
+  }
+
+  /// Sets the [MissingKeyPolicy] of the getters without options.
+  ///
+  /// It also applies to nested `{ ref }` lookups. [Self::resolve] and the
+  /// other methods that take [ResolveOptions] use
+  /// [ResolveOptions::missing_key] instead.
+  ///
+  /// A [MissingKeyPolicy::Fallback] resolver must live as long as the
+  /// program, e.g., in a `static`.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use glossa_dsl::{Resolver, resolver::MissingKeyPolicy};
+  ///
+  /// let mut de: Resolver = [("title", "{ app } Einstellungen")].try_into()?;
+  /// de.set_missing_key_policy(MissingKeyPolicy::Placeholder);
+  /// assert_eq!(de.try_get("title")?, "⟦app⟧ Einstellungen");
+  /// assert_eq!(de.try_get("about")?, "⟦about⟧");
+  ///
+  /// let en: Resolver = [("app", "Glossa"), ("about", "About")].try_into()?;
+  /// let en: &'static Resolver = Box::leak(Box::new(en));
+  /// de.set_missing_key_policy(MissingKeyPolicy::Fallback(en));
+  /// assert_eq!(de.try_get("title")?, "Glossa Einstellungen");
+  /// assert_eq!(de.try_get("about")?, "About");
+  ///
+  /// # Ok::<(), glossa_dsl::Error>(())
+  /// ```
+  pub fn set_missing_key_policy(&mut self, policy: MissingKeyPolicy<'static>) {
+    self.missing_key = policy
+  }
+
+  /// Similar to [Self::set_missing_key_policy], but takes ownership of the
+  /// Self.
+  pub fn with_missing_key_policy(self, policy: MissingKeyPolicy<'static>) -> Self {
+    Self {
+      missing_key: policy,
+      ..self
+    }
+  }
+}
+
+/// Which rendered values are escaped
+///
+/// ## Example
+///
+/// ```
+/// use glossa_dsl::{
+///   Resolver,
+///   escape::HtmlEscaper,
+///   resolver::{EscapeMode, ResolveOptions},
+/// };
+///
+/// let res: Resolver = [("greeting", "<b>Hello</b> {$name}")].try_into()?;
+///
+/// let options = ResolveOptions {
+///   escape: EscapeMode::Parameters(&HtmlEscaper),
+///   ..Default::default()
+/// };
+/// let ctx = [("name", "<script>")];
+/// let resolved = res.resolve("greeting", &ctx, &options)?;
+/// assert_eq!(resolved.text, "<b>Hello</b> &lt;script&gt;");
+///
+/// # Ok::<(), glossa_dsl::Error>(())
+/// ```
+#[derive(Default, Clone, Copy)]
+pub enum EscapeMode<'a> {
+  #[default]
+  Off,
+  /// Escapes parameter values (including substitutes of missing
+  /// parameters), and trusts the text of messages.
+  Parameters(&'a dyn Escaper),
+  /// Escapes the text of messages (including substitutes of missing keys),
+  /// and trusts parameter values.
+  Text(&'a dyn Escaper),
+}
+
+impl fmt::Debug for EscapeMode<'_> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Off => f.write_str("Off"),
+      Self::Parameters(_) => f.write_str("Parameters(..)"),
+      Self::Text(_) => f.write_str("Text(..)"),
+    }
+  }
+}
+
+/// Wraps interpolated values in FSI (U+2068) and PDI (U+2069), so that
+/// e.g. a Latin name does not scramble the direction of an Arabic sentence.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum BidiIsolation {
+  #[default]
+  Off,
+  /// Isolates the values of `{ $param }` placeables.
+  Parameters,
+  /// Isolates parameter values and the text of referenced messages (`{ ref }`
+  /// and `{ ref(..) }`).
+  ParametersAndReferences,
+}
+
+impl BidiIsolation {
+  /// First Strong Isolate
+  pub const FSI: char = '\u{2068}';
+  /// Pop Directional Isolate
+  pub const PDI: char = '\u{2069}';
+
+  /// [Self::Parameters] for right-to-left locales (see [is_rtl_locale]),
+  /// otherwise [Self::Off].
+  pub fn for_locale(locale: &str) -> Self {
+    match is_rtl_locale(locale) {
+      true => Self::Parameters,
+      _ => Self::Off,
+    }
+  }
+}
+
+/// Returns true if the locale (a BCP 47 tag, or a POSIX name like `fa_IR`) is
+/// written right-to-left.
+///
+/// A script subtag takes precedence over the language, e.g., `pa-Arab` is
+/// RTL, and `ku-Latn` is not.
+pub fn is_rtl_locale(locale: &str) -> bool {
+  const RTL_LANGUAGES: &[&str] = &[
+    "ar", "arc", "ckb", "dv", "fa", "he", "iw", "ks", "ps", "sd", "syr", "ug", "ur",
+    "yi",
+  ];
+  const RTL_SCRIPTS: &[&str] =
+    &["adlm", "arab", "hebr", "nkoo", "rohg", "syrc", "thaa"];
+
+  let mut subtags = locale.split(['-', '_']);
+  let language = subtags
+    .next()
+    .unwrap_or_default();
+
+  match subtags.find(|s| {
+    s.len() == 4
+      && s
+        .chars()
+        .all(|c| c.is_ascii_alphabetic())
+  }) {
+    Some(script) => RTL_SCRIPTS
+      .iter()
+      .any(|s| s.eq_ignore_ascii_case(script)),
+    _ => RTL_LANGUAGES
+      .iter()
+      .any(|l| l.eq_ignore_ascii_case(language)),
+  }
+}
+
+/// How a parameter that is not in the context is handled
+///
+/// The substitute is also used as the value of a selector (usually matching
+/// the default branch) and of a call-site argument.
+#[derive(Default, Clone, Copy)]
+pub enum MissingParameterPolicy<'a> {
+  /// Fails with [ResolverError::MissingParameter](crate::Error::MissingParameter).
+  #[default]
+  Error,
+  /// Renders the placeable literally, e.g., `{$name}`.
+  Literal,
+  /// Renders nothing.
+  Empty,
+  /// Renders the value returned by the callback, which receives the name of
+  /// the parameter.
+  Callback(&'a dyn Fn(&str) -> MiniStr),
+}
+
+impl fmt::Debug for MissingParameterPolicy<'_> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Error => f.write_str("Error"),
+      Self::Literal => f.write_str("Literal"),
+      Self::Empty => f.write_str("Empty"),
+      Self::Callback(_) => f.write_str("Callback(..)"),
+    }
+  }
+}
+
+/// How a message key that is not defined is handled
+#[derive(Debug, Default, Clone, Copy)]
+pub enum MissingKeyPolicy<'a> {
+  /// Fails with [ResolverError::UndefinedVariable](crate::Error::UndefinedVariable).
+  #[default]
+  Error,
+  /// Renders the key itself, e.g., `greeting`.
+  Key,
+  /// Renders a marked placeholder, e.g., `⟦greeting⟧`.
+  Placeholder,
+  /// Looks up the key in another resolver, e.g., the source language.
+  ///
+  /// Nested references of a fallback message are looked up in the primary
+  /// resolver first. A key that neither resolver defines is an error.
+  Fallback(&'a Resolver),
+}
+
+/// Result of [Resolver::resolve]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Resolved {
+  pub text: MiniStr,
+  /// Names of the parameters that were missing from the context, and were
+  /// substituted according to [ResolveOptions::missing_parameter]; in order
+  /// of first use
+  pub missing_parameters: Vec<MiniStr>,
+  /// Keys that were not defined, and were handled according to
+  /// [ResolveOptions::missing_key]; in order of first use
+  pub missing_keys: Vec<MiniStr>,
+}
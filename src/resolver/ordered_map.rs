@@ -33,7 +33,7 @@ impl Resolver {
   /// let _inner_data = resolver.into_inner();
   /// ```
   pub fn into_inner(self) -> AST {
-    self.0
+    self.ast
   }
 }
 
@@ -0,0 +1,131 @@
+use alloc::vec::Vec;
+
+use crate::{
+  MiniStr,
+  error::ResolverResult,
+  parsers::process_tmpl::{Output, Processor},
+  part::Markup,
+  resolver::{ResolveOptions, Resolver, lookup_value::with_slice_context},
+};
+
+/// Segment of a rendered message, see [Resolver::resolve_segments]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Segment {
+  /// Text of the message itself, including markup tags
+  Text(MiniStr),
+  /// Value of `{ $name }`
+  Parameter { name: MiniStr, value: MiniStr },
+  /// Whole text of `{ key }` or `{ key(..) }`, including what the referenced
+  /// message interpolates
+  Reference { key: MiniStr, text: MiniStr },
+}
+
+#[derive(Default)]
+struct SegmentBuilder {
+  segments: Vec<Segment>,
+  /// Nesting level of references
+  depth: usize,
+}
+
+impl Output for SegmentBuilder {
+  fn text(&mut self, text: &str) {
+    match (self.depth, self.segments.last_mut()) {
+      (_, _) if text.is_empty() => {}
+      (1.., Some(Segment::Reference { text: prev, .. }))
+      | (0, Some(Segment::Text(prev))) => prev.push_str(text),
+      _ => self
+        .segments
+        .push(Segment::Text(text.into())),
+    }
+  }
+
+  fn parameter(&mut self, name: &str, value: &str) {
+    match self.depth {
+      0 => self
+        .segments
+        .push(Segment::Parameter {
+          name: name.into(),
+          value: value.into(),
+        }),
+      _ => self.text(value),
+    }
+  }
+
+  fn markup(&mut self, _markup: &Markup, source: &str) {
+    self.text(source)
+  }
+
+  fn enter_reference(&mut self, key: &str) {
+    if self.depth == 0 {
+      self
+        .segments
+        .push(Segment::Reference {
+          key: key.into(),
+          text: MiniStr::default(),
+        })
+    }
+    self.depth += 1
+  }
+
+  fn exit_reference(&mut self, _key: &str) {
+    self.depth -= 1
+  }
+}
+
+impl Resolver {
+  /// Similar to [Self::resolve], but returns an ordered list of segments, so
+  /// that e.g. a GUI can style interpolated values differently from the
+  /// surrounding text.
+  ///
+  /// Concatenating the segments yields the text of [Self::resolve].
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use glossa_dsl::{Resolver, resolver::Segment};
+  ///
+  /// let res: Resolver = [
+  ///   ("app", "Glossa"),
+  ///   ("welcome", "Welcome to { app }, {$name}!"),
+  /// ]
+  /// .try_into()?;
+  ///
+  /// let segments =
+  ///   res.resolve_segments("welcome", &[("name", "Tom")], &Default::default())?;
+  ///
+  /// assert_eq!(
+  ///   segments,
+  ///   [
+  ///     Segment::Text("Welcome to ".into()),
+  ///     Segment::Reference {
+  ///       key: "app".into(),
+  ///       text: "Glossa".into()
+  ///     },
+  ///     Segment::Text(", ".into()),
+  ///     Segment::Parameter {
+  ///       name: "name".into(),
+  ///       value: "Tom".into()
+  ///     },
+  ///     Segment::Text("!".into()),
+  ///   ]
+  /// );
+  ///
+  /// # Ok::<(), glossa_dsl::Error>(())
+  /// ```
+  pub fn resolve_segments(
+    &self,
+    var_name: &str,
+    context: &[(&str, &str)],
+    options: &ResolveOptions<'_>,
+  ) -> ResolverResult<Vec<Segment>> {
+    with_slice_context(context, |ctx| {
+      let mut builder = SegmentBuilder::default();
+      Processor::new(self, options).try_get_template_and_process(
+        var_name,
+        ctx,
+        &mut builder,
+      )?;
+      Ok(builder.segments)
+    })
+  }
+}
@@ -0,0 +1,254 @@
+use std::sync::Arc;
+
+use arc_swap::ArcSwap;
+use tap::Pipe;
+
+use crate::{
+  MiniStr,
+  error::{ResolverError, ResolverResult},
+  resolver::{BTreeRawMap, Node, ResolveOptions, Resolved, Resolver, Segment},
+};
+
+/// Thread-safe handle to a [Resolver] that can be replaced at runtime.
+///
+/// ## Design Notes
+///
+/// - Cloning the handle is cheap; all clones share the same resolver.
+/// - Reads are lock-free: rendering never waits for a replacement.
+/// - Replacement is atomic (RCU-style): a reader sees either the old or
+///   the new messages, never a mix of both. Snapshots taken before a
+///   replacement stay valid.
+/// - The rendering methods of [Resolver] are mirrored. For anything else
+///   (e.g., [Resolver::validate]), call it on a snapshot from [Self::load].
+///
+/// ## Example
+///
+/// ```
+/// use glossa_dsl::{Resolver, SharedResolver};
+///
+/// let shared = SharedResolver::new([("greeting", "Hello {$name}")].try_into()?);
+/// let reader = shared.clone();
+///
+/// let worker = std::thread::spawn(move || {
+///   reader.get_with_context("greeting", &[("name", "Tom")])
+/// });
+/// let text = worker.join().expect("worker panicked")?;
+/// assert!(text == "Hello Tom" || text == "Hi Tom");
+///
+/// let updated: Resolver = [("greeting", "Hi {$name}")].try_into()?;
+/// shared.store(updated);
+/// assert_eq!(shared.get_with_context("greeting", &[("name", "Tom")])?, "Hi Tom");
+///
+/// # Ok::<(), glossa_dsl::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct SharedResolver(Arc<ArcSwap<Resolver>>);
+
+impl Default for SharedResolver {
+  fn default() -> Self {
+    Self::new(Resolver::default())
+  }
+}
+
+impl From<Resolver> for SharedResolver {
+  fn from(value: Resolver) -> Self {
+    Self::new(value)
+  }
+}
+
+impl SharedResolver {
+  pub fn new(resolver: Resolver) -> Self {
+    resolver
+      .pipe(ArcSwap::from_pointee)
+      .pipe(Arc::new)
+      .pipe(Self)
+  }
+
+  /// Returns a snapshot of the current resolver.
+  ///
+  /// The snapshot is not affected by later replacements.
+  pub fn load(&self) -> Arc<Resolver> {
+    self.0.load_full()
+  }
+
+  /// Replaces the resolver, and returns the previous one.
+  pub fn store(&self, resolver: Resolver) -> Arc<Resolver> {
+    resolver
+      .pipe(Arc::new)
+      .pipe(|new| self.0.swap(new))
+  }
+
+  /// Replaces the resolver with a new version derived from the current
+  /// one, e.g., to [merge](Resolver::merge) overrides.
+  ///
+  /// `f` may be called more than once if another thread replaces the
+  /// resolver concurrently. Returns the previous resolver.
+  pub fn update<F>(&self, mut f: F) -> Arc<Resolver>
+  where
+    F: FnMut(&Resolver) -> Resolver,
+  {
+    self.0.rcu(|current| f(current))
+  }
+
+  /// See [Resolver::get_with_context]
+  pub fn get_with_context(
+    &self,
+    var_name: &str,
+    context: &[(&str, &str)],
+  ) -> ResolverResult<MiniStr> {
+    self
+      .0
+      .load()
+      .get_with_context(var_name, context)
+  }
+
+  /// See [Resolver::get_with_ctx_btree_map]
+  pub fn get_with_ctx_btree_map(
+    &self,
+    var_name: &str,
+    context_map: &BTreeRawMap,
+  ) -> ResolverResult<MiniStr> {
+    self
+      .0
+      .load()
+      .get_with_ctx_btree_map(var_name, context_map)
+  }
+
+  /// See [Resolver::get_with_ctx_map]
+  pub fn get_with_ctx_map(
+    &self,
+    var_name: &str,
+    context_map: &crate::ContextMap,
+  ) -> ResolverResult<MiniStr> {
+    self
+      .0
+      .load()
+      .get_with_ctx_map(var_name, context_map)
+  }
+
+  /// See [Resolver::get_with_ctx_map_buf]
+  pub fn get_with_ctx_map_buf(
+    &self,
+    var_name: &str,
+    context_map: &crate::ContextMapBuf,
+  ) -> ResolverResult<MiniStr> {
+    self
+      .0
+      .load()
+      .get_with_ctx_map_buf(var_name, context_map)
+  }
+
+  /// See [Resolver::try_get]
+  pub fn try_get(&self, var_name: &str) -> ResolverResult<MiniStr> {
+    self.0.load().try_get(var_name)
+  }
+
+  /// See [Resolver::resolve]
+  pub fn resolve(
+    &self,
+    var_name: &str,
+    context: &[(&str, &str)],
+    options: &ResolveOptions<'_>,
+  ) -> ResolverResult<Resolved> {
+    self
+      .0
+      .load()
+      .resolve(var_name, context, options)
+  }
+
+  /// See [Resolver::resolve_lossy]
+  pub fn resolve_lossy(
+    &self,
+    var_name: &str,
+    context: &[(&str, &str)],
+    options: &ResolveOptions<'_>,
+  ) -> (Resolved, Vec<ResolverError>) {
+    self
+      .0
+      .load()
+      .resolve_lossy(var_name, context, options)
+  }
+
+  /// See [Resolver::resolve_segments]
+  pub fn resolve_segments(
+    &self,
+    var_name: &str,
+    context: &[(&str, &str)],
+    options: &ResolveOptions<'_>,
+  ) -> ResolverResult<Vec<Segment>> {
+    self
+      .0
+      .load()
+      .resolve_segments(var_name, context, options)
+  }
+
+  /// See [Resolver::resolve_tree]
+  pub fn resolve_tree(
+    &self,
+    var_name: &str,
+    context: &[(&str, &str)],
+    options: &ResolveOptions<'_>,
+  ) -> ResolverResult<Vec<Node>> {
+    self
+      .0
+      .load()
+      .resolve_tree(var_name, context, options)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_snapshot_survives_store() -> ResolverResult<()> {
+    let shared = SharedResolver::new([("h", "Hello")].try_into()?);
+    let snapshot = shared.load();
+
+    let prev = shared.store([("h", "Hi")].try_into()?);
+    assert_eq!(prev.try_get("h")?, "Hello");
+    assert_eq!(snapshot.try_get("h")?, "Hello");
+    assert_eq!(shared.try_get("h")?, "Hi");
+
+    shared.update(|current| {
+      let mut new = current.clone();
+      let _ = new.merge(
+        [("w", "World")]
+          .try_into()
+          .expect("Invalid slice"),
+        crate::resolver::MergePolicy::KeepLast,
+      );
+      new
+    });
+    assert_eq!(shared.try_get("w")?, "World");
+    assert_eq!(shared.try_get("h")?, "Hi");
+    Ok(())
+  }
+
+  #[test]
+  fn test_resolve_with_options() -> ResolverResult<()> {
+    use crate::resolver::MissingParameterPolicy;
+
+    let shared = SharedResolver::new([("hi", "<b>Hi</b> {$name}")].try_into()?);
+    let options = ResolveOptions {
+      missing_parameter: MissingParameterPolicy::Literal,
+      ..Default::default()
+    };
+
+    let resolved = shared.resolve("hi", &[], &options)?;
+    assert_eq!(resolved.text, "<b>Hi</b> {$name}");
+    assert_eq!(resolved.missing_parameters, ["name"]);
+
+    let (resolved, errors) = shared.resolve_lossy("hi", &[], &Default::default());
+    assert_eq!(resolved.text, "<b>Hi</b> {$name}");
+    assert_eq!(errors.len(), 1);
+
+    let ctx = [("name", "Tom")];
+    let segments = shared.resolve_segments("hi", &ctx, &Default::default())?;
+    assert_eq!(segments.len(), 2);
+
+    let tree = shared.resolve_tree("hi", &ctx, &Default::default())?;
+    assert!(matches!(&tree[0], Node::Element { name, .. } if name == "b"));
+    Ok(())
+  }
+}
@@ -108,7 +108,7 @@ impl Resolver {
       })
       // .tap_dbg(|x| println!("{:?}", x.size_hint()))
       .collect::<Result<AST, _>>()?
-      .pipe(Self)
+      .pipe(Self::from)
       .pipe(Ok)
   }
 }
@@ -0,0 +1,167 @@
+use alloc::vec::Vec;
+
+use crate::{
+  MiniStr,
+  error::ResolverResult,
+  parsers::process_tmpl::{Output, Processor},
+  part::Markup,
+  resolver::{ResolveOptions, Resolver, lookup_value::with_slice_context},
+};
+
+/// Node of a rendered message, see [Resolver::resolve_tree]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Node {
+  /// Text of the message, including the text of referenced messages
+  Text(MiniStr),
+  /// Value of `{ $name }`
+  Parameter { name: MiniStr, value: MiniStr },
+  /// `<name>...</name>`, or `<name/>` without children
+  Element { name: MiniStr, children: Vec<Node> },
+}
+
+/// Builds the tree from the flat output of [Processor].
+#[derive(Default)]
+struct TreeBuilder {
+  /// Open elements: (name, children)
+  open: Vec<(MiniStr, Vec<Node>)>,
+  root: Vec<Node>,
+}
+
+impl TreeBuilder {
+  fn children(&mut self) -> &mut Vec<Node> {
+    match self.open.last_mut() {
+      Some((_, children)) => children,
+      _ => &mut self.root,
+    }
+  }
+
+  /// Closes the innermost open element.
+  fn close(&mut self) {
+    if let Some((name, children)) = self.open.pop() {
+      self
+        .children()
+        .push(Node::Element { name, children })
+    }
+  }
+
+  /// Closes unclosed elements at the end of the message.
+  fn finish(mut self) -> Vec<Node> {
+    while !self.open.is_empty() {
+      self.close()
+    }
+    self.root
+  }
+}
+
+impl Output for TreeBuilder {
+  fn text(&mut self, text: &str) {
+    if text.is_empty() {
+      return;
+    }
+    let children = self.children();
+    match children.last_mut() {
+      Some(Node::Text(prev)) => prev.push_str(text),
+      _ => children.push(Node::Text(text.into())),
+    }
+  }
+
+  fn parameter(&mut self, name: &str, value: &str) {
+    self
+      .children()
+      .push(Node::Parameter {
+        name: name.into(),
+        value: value.into(),
+      })
+  }
+
+  fn markup(&mut self, markup: &Markup, source: &str) {
+    match markup {
+      Markup::Open(name) => self
+        .open
+        .push((name.clone(), Vec::new())),
+      Markup::Standalone(name) => self
+        .children()
+        .push(Node::Element {
+          name: name.clone(),
+          children: Vec::new(),
+        }),
+      Markup::Close(name) => match self
+        .open
+        .iter()
+        .rposition(|(open, _)| open == name)
+      {
+        // Elements opened inside it, but not closed, are closed as well.
+        Some(idx) => (idx..self.open.len()).for_each(|_| self.close()),
+        // A close tag without an open tag is text.
+        _ => self.text(source),
+      },
+    }
+  }
+}
+
+impl Resolver {
+  /// Similar to [Self::resolve], but returns a tree of text, parameter and
+  /// element nodes instead of a flat string.
+  ///
+  /// Markup tags (e.g., `<link>terms</link>`) become [Node::Element], so that
+  /// a UI framework can map `link` to its own widget. Referenced messages are
+  /// inlined.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use glossa_dsl::{Resolver, resolver::Node};
+  ///
+  /// let res: Resolver = [
+  ///   ("terms", "terms of service"),
+  ///   ("agree", "Hi <b>{$name}</b>, read the <link>{ terms }</link>.<br/>"),
+  /// ]
+  /// .try_into()?;
+  ///
+  /// let ctx = [("name", "Tom")];
+  /// assert_eq!(
+  ///   res.get_with_context("agree", &ctx)?,
+  ///   "Hi <b>Tom</b>, read the <link>terms of service</link>.<br/>"
+  /// );
+  ///
+  /// let tree = res.resolve_tree("agree", &ctx, &Default::default())?;
+  /// assert_eq!(
+  ///   tree,
+  ///   [
+  ///     Node::Text("Hi ".into()),
+  ///     Node::Element {
+  ///       name: "b".into(),
+  ///       children: vec![Node::Parameter {
+  ///         name: "name".into(),
+  ///         value: "Tom".into()
+  ///       }],
+  ///     },
+  ///     Node::Text(", read the ".into()),
+  ///     Node::Element {
+  ///       name: "link".into(),
+  ///       children: vec![Node::Text("terms of service".into())],
+  ///     },
+  ///     Node::Text(".".into()),
+  ///     Node::Element {
+  ///       name: "br".into(),
+  ///       children: vec![],
+  ///     },
+  ///   ]
+  /// );
+  ///
+  /// # Ok::<(), glossa_dsl::Error>(())
+  /// ```
+  pub fn resolve_tree(
+    &self,
+    var_name: &str,
+    context: &[(&str, &str)],
+    options: &ResolveOptions<'_>,
+  ) -> ResolverResult<Vec<Node>> {
+    with_slice_context(context, |ctx| {
+      let mut tree = TreeBuilder::default();
+      Processor::new(self, options)
+        .try_get_template_and_process(var_name, ctx, &mut tree)?;
+      Ok(tree.finish())
+    })
+  }
+}
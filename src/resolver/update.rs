@@ -0,0 +1,105 @@
+use tap::Pipe;
+
+use crate::{
+  error::{ResolverError, ResolverResult},
+  parsers::parse_value_or_map_err,
+  resolver::{Resolver, from_slice::convert_map_key},
+  template::Template,
+};
+
+impl Resolver {
+  /// Parses `value`, and inserts it as the message `key`.
+  ///
+  /// Returns the previous template of `key`, if any. On a parse error, the
+  /// resolver is not modified.
+  ///
+  /// References are not validated; call [Self::validate_key] to check
+  /// only the messages affected by this change.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use glossa_dsl::Resolver;
+  ///
+  /// let mut res: Resolver = [("greeting", "{ h } { $name }")].try_into()?;
+  ///
+  /// assert!(res.insert_source("h", "Hello")?.is_none());
+  /// res.validate_key("h")?;
+  ///
+  /// let text = res.get_with_context("greeting", &[("name", "Tom")])?;
+  /// assert_eq!(text, "Hello Tom");
+  ///
+  /// # Ok::<(), glossa_dsl::Error>(())
+  /// ```
+  pub fn insert_source(
+    &mut self,
+    key: &str,
+    value: &str,
+  ) -> ResolverResult<Option<Template>> {
+    parse_value_or_map_err(key, value)?
+      .pipe(|tmpl| {
+        self
+          .ast
+          .insert(convert_map_key(key), tmpl)
+      })
+      .pipe(Ok)
+  }
+
+  /// Similar to [Self::insert_source], but `key` must already exist.
+  ///
+  /// Returns the previous template, or [ResolverError::UndefinedVariable].
+  pub fn replace(&mut self, key: &str, value: &str) -> ResolverResult<Template> {
+    let tmpl = parse_value_or_map_err(key, value)?;
+
+    match self.ast.get_mut(key) {
+      Some(old) => core::mem::replace(old, tmpl).pipe(Ok),
+      _ => ResolverError::UndefinedVariable(key.into()).pipe(Err),
+    }
+  }
+
+  /// Removes the message `key`, and returns its template.
+  ///
+  /// Other messages may still reference it; see [Self::validate_key].
+  pub fn remove(&mut self, key: &str) -> Option<Template> {
+    self.ast.remove(key)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_update_single_messages() -> ResolverResult<()> {
+    let mut res: Resolver = [("a", "{ b }"), ("b", "B")].try_into()?;
+
+    // A parse error keeps the old message.
+    assert!(
+      res
+        .replace("b", "{{b}")
+        .is_err()
+    );
+    assert_eq!(res.try_get("a")?, "B");
+
+    res.replace("b", "{ c }")?;
+    assert!(matches!(
+      res.validate_key("b"),
+      Err(ResolverError::UndefinedReference(k, r)) if k == "b" && r == "c"
+    ));
+
+    res.insert_source("c", "{ a }")?;
+    assert!(matches!(
+      res.validate_key("c"),
+      Err(ResolverError::CyclicReference(path)) if path == "c -> a -> b -> c"
+    ));
+
+    assert!(
+      res
+        .replace("missing", "x")
+        .is_err()
+    );
+    assert!(res.remove("c").is_some());
+    assert!(res.remove("c").is_none());
+    Ok(())
+  }
+}
@@ -0,0 +1,184 @@
+use alloc::{collections::BTreeMap, vec::Vec};
+
+use compact_str::format_compact;
+use tap::Pipe;
+
+use crate::{
+  MiniStr,
+  error::{ResolverError, ResolverResult},
+  resolver::Resolver,
+};
+
+/// DFS state of a message during cycle detection
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Visit {
+  InProgress,
+  Done,
+}
+
+impl Resolver {
+  /// Checks that every referenced message exists, and that no message
+  /// references itself (directly or indirectly).
+  ///
+  /// Returns the first problem found. See also:
+  /// [Self::validation_errors]
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use glossa_dsl::{Error, Resolver};
+  ///
+  /// let res: Resolver = [("a", "{ b }"), ("b", "{ a }")].try_into()?;
+  ///
+  /// let err = res.validate().unwrap_err();
+  /// assert!(matches!(err, Error::CyclicReference(path) if path == "a -> b -> a"));
+  ///
+  /// # Ok::<(), glossa_dsl::Error>(())
+  /// ```
+  pub fn validate(&self) -> ResolverResult<()> {
+    match self
+      .validation_errors()
+      .into_iter()
+      .next()
+    {
+      Some(e) => Err(e),
+      _ => Ok(()),
+    }
+  }
+
+  /// Similar to [Self::validate], but collects all problems.
+  ///
+  /// Messages are checked in key order, so the result is deterministic.
+  ///
+  /// - [ResolverError::UndefinedReference]
+  /// - [ResolverError::CyclicReference]
+  pub fn validation_errors(&self) -> Vec<ResolverError> {
+    let sorted = self
+      .iter()
+      .map(|(k, v)| (k.as_str(), v))
+      .collect::<BTreeMap<_, _>>();
+
+    let mut errors = sorted
+      .iter()
+      .flat_map(|(&key, tmpl)| {
+        tmpl
+          .references()
+          .into_iter()
+          .filter(|r| !self.contains_key(*r))
+          .map(move |r| ResolverError::UndefinedReference(key.into(), r.into()))
+      })
+      .collect::<Vec<_>>();
+
+    let mut states = BTreeMap::new();
+    for &key in sorted.keys() {
+      let mut path = Vec::new();
+      self.find_cycles(key, &mut states, &mut path, &mut errors);
+    }
+    errors
+  }
+
+  /// Similar to [Self::validate], but only checks the messages affected by
+  /// a change of `key`:
+  ///
+  /// - If `key` exists: every message reachable from it, including cycles
+  ///   through it.
+  /// - If `key` does not exist (e.g., it was removed): the messages that
+  ///   reference it.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use glossa_dsl::{Error, Resolver};
+  ///
+  /// let mut res: Resolver = [("h", "Hello"), ("greeting", "{ h }!")].try_into()?;
+  ///
+  /// res.remove("h");
+  /// let err = res.validate_key("h").unwrap_err();
+  /// assert!(matches!(err, Error::UndefinedReference(key, r) if key == "greeting" && r == "h"));
+  ///
+  /// res.insert_source("h", "Hi")?;
+  /// res.validate_key("h")?;
+  ///
+  /// # Ok::<(), glossa_dsl::Error>(())
+  /// ```
+  pub fn validate_key(&self, key: &str) -> ResolverResult<()> {
+    if !self.contains_key(key) {
+      return match self
+        .iter()
+        .filter(|(_, tmpl)| tmpl.references().contains(&key))
+        .map(|(k, _)| k.as_str())
+        .min()
+      {
+        Some(referrer) => {
+          ResolverError::UndefinedReference(referrer.into(), key.into()).pipe(Err)
+        }
+        _ => Ok(()),
+      };
+    }
+
+    let mut errors = Vec::new();
+    let mut states = BTreeMap::new();
+    self.find_cycles(key, &mut states, &mut Vec::new(), &mut errors);
+
+    // `states` contains every existing message reachable from `key`.
+    let undefined = states.keys().flat_map(|&k| {
+      self[k]
+        .references()
+        .into_iter()
+        .filter(|r| !self.contains_key(*r))
+        .map(move |r| ResolverError::UndefinedReference(k.into(), r.into()))
+    });
+
+    match undefined.chain(errors).next() {
+      Some(e) => Err(e),
+      _ => Ok(()),
+    }
+  }
+
+  fn find_cycles<'a>(
+    &'a self,
+    key: &'a str,
+    states: &mut BTreeMap<&'a str, Visit>,
+    path: &mut Vec<&'a str>,
+    errors: &mut Vec<ResolverError>,
+  ) {
+    match states.get(key) {
+      Some(Visit::Done) => return,
+      Some(Visit::InProgress) => {
+        errors.push(cyclic_reference(path, key));
+        return;
+      }
+      _ => {}
+    }
+
+    let Some(tmpl) = self.get(key) else { return };
+
+    states.insert(key, Visit::InProgress);
+    path.push(key);
+
+    for r in tmpl.references() {
+      self.find_cycles(r, states, path, errors);
+    }
+
+    path.pop();
+    states.insert(key, Visit::Done);
+  }
+}
+
+/// Creates a [ResolverError::CyclicReference] with the path from the first
+/// occurrence of `key` in `path` back to `key`, e.g., `a -> b -> a`.
+pub(crate) fn cyclic_reference(path: &[&str], key: &str) -> ResolverError {
+  let start = path
+    .iter()
+    .position(|&k| k == key)
+    .unwrap_or_default();
+
+  path[start..]
+    .iter()
+    .chain([&key])
+    .fold(MiniStr::const_new(""), |acc, k| match acc.is_empty() {
+      true => (*k).into(),
+      _ => format_compact!("{acc} -> {k}"),
+    })
+    .pipe(ResolverError::CyclicReference)
+}
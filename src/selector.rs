@@ -4,6 +4,9 @@ use crate::{MiniStr, template};
 
 /// Conditional branching structure
 ///
+/// The fields added in 0.0.17 have serde defaults, so that a selector
+/// serialized (e.g., to JSON) by 0.0.16 can still be deserialized.
+///
 /// ## Memory Layout
 ///
 /// - Uses Box<[]> for case storage
@@ -17,10 +20,56 @@ use crate::{MiniStr, template};
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Selector {
-  /// Parameter name to check
+  /// Parameter name to check, or the key of a message if
+  /// [Self::input] is [SelectorInput::Reference]
   pub(crate) param: MiniStr,
+  /// Where the value to check comes from
+  #[cfg_attr(feature = "serde", serde(default))]
+  pub(crate) input: SelectorInput,
   /// (Value pattern, Template) pairs
   pub(crate) cases: Box<[(MiniStr, template::Template)]>,
   /// Fallback template when no cases match
   pub(crate) default: Option<Box<template::Template>>,
+  /// Value pattern of the default branch, e.g., `other` in `*[other]`
+  ///
+  /// It is only used to write the source back.
+  #[cfg_attr(feature = "serde", serde(default))]
+  pub(crate) default_key: MiniStr,
+  /// Translator notes from `# ...` lines, each with the position it is
+  /// written at
+  #[cfg_attr(feature = "serde", serde(default))]
+  pub(crate) comments: Box<[(CommentAnchor, MiniStr)]>,
+}
+
+/// Position of a translator note in a [Selector]
+///
+/// A note belongs to the branch that follows it, so it stays with that
+/// branch when the default branch is moved to the end. The notes of a
+/// selector are sorted in this order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) enum CommentAnchor {
+  /// Before `$param ->`
+  Head,
+  /// Before the case at the index of [Selector::cases]
+  Case(usize),
+  /// Before the default branch
+  Default,
+  /// After the last branch
+  End,
+}
+
+/// Input of a [Selector]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SelectorInput {
+  /// `$gender ->` checks a parameter of the context.
+  #[default]
+  Parameter,
+  /// `{ -brand.gender -> ... }` checks the rendered text of another message,
+  /// e.g., the grammatical gender of a product name.
+  ///
+  /// The text is rendered without escaping and bidi isolation, so that it
+  /// matches the keys of the branches.
+  Reference,
 }
@@ -1,8 +1,16 @@
-use compact_str::ToCompactString;
+use alloc::{boxed::Box, vec::Vec};
+use compact_str::{ToCompactString, format_compact};
+
 use nom::{
   IResult, Parser,
-  bytes::complete::{tag, take_till, take_until, take_while},
-  sequence::delimited,
+  branch::alt,
+  bytes::complete::{
+    tag, take_till, take_until, take_while, take_while_m_n, take_while1,
+  },
+  character::complete::{char, multispace0},
+  combinator::{cut, map_opt, opt, recognize, verify},
+  multi::separated_list0,
+  sequence::{delimited, preceded, separated_pair},
 };
 use tap::Pipe;
 use tinyvec::TinyVec;
@@ -10,8 +18,8 @@ use tinyvec::TinyVec;
 use crate::{
   MiniStr,
   error::{ResolverError, ResolverResult},
-  part::{TemplatePart, VariableRef},
-  selector,
+  part::{Call, CallArg, Markup, TemplatePart, VariableRef},
+  selector::{self, CommentAnchor, SelectorInput},
 };
 pub(crate) type TinyTemplateParts = TinyVec<[TemplatePart; 5]>;
 
@@ -39,6 +47,222 @@ impl Default for Template {
   }
 }
 
+impl Template {
+  /// Collects the translator notes of the template (including nested
+  /// branches), in source order. The default branch of a selector comes
+  /// last, together with its notes.
+  ///
+  /// Comments are written as `{# ... #}` in text, or as `# ...` lines in a
+  /// selector. They never appear in the rendered output.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use glossa_dsl::Resolver;
+  ///
+  /// let res: Resolver = [(
+  ///   "unread",
+  ///   "# Shown in the inbox header
+  ///   $num ->
+  ///     [0] No messages.{# keep it short #}
+  ///     *[other] {$num} messages.",
+  /// )]
+  /// .try_into()?;
+  ///
+  /// let comments = res["unread"].comments();
+  /// assert_eq!(comments, ["Shown in the inbox header", "keep it short"]);
+  ///
+  /// assert_eq!(res.get_with_context("unread", &[("num", "0")])?, "No messages.");
+  ///
+  /// # Ok::<(), glossa_dsl::Error>(())
+  /// ```
+  pub fn comments(&self) -> Vec<&str> {
+    let mut comments = Vec::new();
+    self.collect_comments(&mut comments);
+    comments
+  }
+
+  /// Collects the keys of the messages that the template references
+  /// (including nested branches and parameterised references), in source
+  /// order and without duplicates.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use glossa_dsl::Resolver;
+  ///
+  /// let res: Resolver = [
+  ///   ("greeting", "{ time-period(period: $when) }! { gender }{ $name }"),
+  ///   ("gender", "$attr ->\n [male] Mr.\n *[female] { ms }"),
+  ///   ("time-period", "Good {$period}"),
+  ///   ("ms", "Ms."),
+  /// ]
+  /// .try_into()?;
+  ///
+  /// assert_eq!(res["greeting"].references(), ["time-period", "gender"]);
+  /// assert_eq!(res["gender"].references(), ["ms"]);
+  ///
+  /// # Ok::<(), glossa_dsl::Error>(())
+  /// ```
+  pub fn references(&self) -> Vec<&str> {
+    let mut refs = Vec::new();
+    self.collect_references(&mut refs);
+    refs
+  }
+
+  fn collect_references<'a>(&'a self, refs: &mut Vec<&'a str>) {
+    let mut push = |name: &'a str| {
+      if !refs.contains(&name) {
+        refs.push(name)
+      }
+    };
+
+    match self {
+      Self::Parts(parts) => parts
+        .iter()
+        .for_each(|part| match part {
+          TemplatePart::Variable(VariableRef::Variable(name)) => push(name),
+          TemplatePart::Variable(VariableRef::Call(call)) => push(&call.name),
+          _ => {}
+        }),
+      Self::Conditional(selector) => {
+        if selector.input == SelectorInput::Reference {
+          push(&selector.param)
+        }
+        selector
+          .cases
+          .iter()
+          .map(|(_, tmpl)| tmpl)
+          .chain(selector.default.as_deref())
+          .for_each(|tmpl| tmpl.collect_references(refs))
+      }
+    }
+  }
+
+  /// Collects the names of the parameters that the template uses directly:
+  /// `{ $name }` placeables, selector parameters, and parameters passed to
+  /// parameterised references. They are in source order and without
+  /// duplicates.
+  ///
+  /// Parameters of referenced messages are not included.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use glossa_dsl::Resolver;
+  ///
+  /// let res: Resolver = [
+  ///   ("greeting", "{ time-period(period: $when) }! { gender }{ $name }"),
+  ///   ("gender", "$attr ->\n [male] Mr.\n *[female] Ms. {$name}"),
+  ///   ("time-period", "Good {$period}"),
+  /// ]
+  /// .try_into()?;
+  ///
+  /// assert_eq!(res["greeting"].parameters(), ["when", "name"]);
+  /// assert_eq!(res["gender"].parameters(), ["attr", "name"]);
+  ///
+  /// # Ok::<(), glossa_dsl::Error>(())
+  /// ```
+  pub fn parameters(&self) -> Vec<&str> {
+    let mut params = Vec::new();
+    self.collect_parameters(&mut params);
+    params
+  }
+
+  fn collect_parameters<'a>(&'a self, params: &mut Vec<&'a str>) {
+    let mut push = |name: &'a str| {
+      if !params.contains(&name) {
+        params.push(name)
+      }
+    };
+
+    match self {
+      Self::Parts(parts) => parts
+        .iter()
+        .for_each(|part| match part {
+          TemplatePart::Variable(VariableRef::Parameter(name)) => push(name),
+          TemplatePart::Variable(VariableRef::Call(call)) => call
+            .args
+            .iter()
+            .filter_map(|(_, arg)| match arg {
+              CallArg::Parameter(name) => Some(name.as_str()),
+              CallArg::Literal(_) => None,
+            })
+            .for_each(&mut push),
+          _ => {}
+        }),
+      Self::Conditional(selector) => {
+        if selector.input == SelectorInput::Parameter {
+          push(&selector.param)
+        }
+        selector
+          .cases
+          .iter()
+          .map(|(_, tmpl)| tmpl)
+          .chain(selector.default.as_deref())
+          .for_each(|tmpl| tmpl.collect_parameters(params))
+      }
+    }
+  }
+
+  fn collect_comments<'a>(&'a self, comments: &mut Vec<&'a str>) {
+    match self {
+      Self::Parts(parts) => parts
+        .iter()
+        .filter_map(|part| match part {
+          TemplatePart::Comment(c) => Some(c.as_str()),
+          _ => None,
+        })
+        .pipe(|iter| comments.extend(iter)),
+      Self::Conditional(selector) => {
+        let extend_at = |anchor, comments: &mut Vec<&'a str>| {
+          selector
+            .comments
+            .iter()
+            .filter(|(a, _)| *a == anchor)
+            .map(|(_, c)| c.as_str())
+            .pipe(|iter| comments.extend(iter))
+        };
+
+        extend_at(CommentAnchor::Head, comments);
+        selector
+          .cases
+          .iter()
+          .enumerate()
+          .map(|(idx, (_, tmpl))| (CommentAnchor::Case(idx), tmpl))
+          .chain(
+            selector
+              .default
+              .as_deref()
+              .map(|tmpl| (CommentAnchor::Default, tmpl)),
+          )
+          .for_each(|(anchor, tmpl)| {
+            extend_at(anchor, comments);
+            tmpl.collect_comments(comments)
+          });
+        extend_at(CommentAnchor::End, comments);
+      }
+    }
+  }
+}
+
+/// Whitespace trim markers of a placeable
+///
+/// - `{~ ... }` trims the whitespace at the end of the preceding text
+/// - `{ ... ~}` trims the whitespace at the start of the following text
+#[derive(Debug, Default, Clone, Copy)]
+struct Trim {
+  before: bool,
+  after: bool,
+}
+
+/// Piece of a template before the trim markers are applied
+enum Piece<'a> {
+  /// Plain text from the source, which is affected by trim markers
+  Text(&'a str),
+  Part(TemplatePart, Trim),
+}
+
 #[allow(clippy::unnecessary_lazy_evaluations)]
 pub(crate) fn parse_template(input: &str) -> ResolverResult<TinyTemplateParts> {
   let mut remaining = input;
@@ -46,23 +270,56 @@ pub(crate) fn parse_template(input: &str) -> ResolverResult<TinyTemplateParts> {
   core::iter::from_fn(|| {
     (!remaining.is_empty()).then(|| ())?;
 
+    if let Ok((next, comment)) = parse_comment(remaining) {
+      remaining = next;
+      return comment
+        .pipe(MiniStr::from)
+        .pipe(TemplatePart::Comment)
+        .pipe(|part| Piece::Part(part, Trim::default()))
+        .pipe(Ok)
+        .into();
+    }
+
+    if let Ok((next, markup)) = parse_markup(remaining) {
+      remaining = next;
+      return markup
+        .pipe(Box::new)
+        .pipe(TemplatePart::Markup)
+        .pipe(|part| Piece::Part(part, Trim::default()))
+        .pipe(Ok)
+        .into();
+    }
+
+    if let Ok((next, (literal, trim))) = parse_literal_placeable(remaining) {
+      remaining = next;
+      return literal
+        .pipe(TemplatePart::Text)
+        .pipe(|part| Piece::Part(part, trim))
+        .pipe(Ok)
+        .into();
+    }
+
     match parse_variable(remaining) {
-      Ok((next, var)) => {
+      Ok((next, (var, trim))) => {
         remaining = next;
         var
           .pipe(TemplatePart::Variable)
+          .pipe(|part| Piece::Part(part, trim))
           .pipe(Ok)
           .into()
       }
+      Err(nom::Err::Failure(e)) => {
+        format_compact!("Invalid call argument at `{}`", e.input)
+          .pipe(ResolverError::ParseError)
+          .pipe(Err)
+          .into()
+      }
       Err(_) => parse_text(remaining)
         .map(|(next, text)| {
           remaining = next;
           match text.is_empty() {
             true => None,
-            _ => text
-              .pipe(MiniStr::from)
-              .pipe(TemplatePart::Text)
-              .into(),
+            _ => text.pipe(Piece::Text).into(),
           }
         })
         .map_err(|e| {
@@ -72,10 +329,67 @@ pub(crate) fn parse_template(input: &str) -> ResolverResult<TinyTemplateParts> {
         .transpose(),
     }
   })
-  .collect()
+  // Adjacent text is merged into a single part.
+  //
+  // `raw_tail` is the length of the source text at the end of the last
+  // part. Trim markers only remove whitespace from the source text, not
+  // from string literals.
+  .try_fold(
+    (TinyTemplateParts::new(), Trim::default(), 0),
+    |(mut parts, prev_trim, raw_tail), piece| match piece? {
+      Piece::Text(text) => {
+        let text = match prev_trim.after {
+          true => text.trim_start(),
+          _ => text,
+        };
+        let raw_tail = match parts.last_mut() {
+          Some(TemplatePart::Text(prev)) => {
+            prev.push_str(text);
+            raw_tail + text.len()
+          }
+          _ if text.is_empty() => 0,
+          _ => {
+            text
+              .pipe(MiniStr::from)
+              .pipe(TemplatePart::Text)
+              .pipe(|part| parts.push(part));
+            text.len()
+          }
+        };
+        Ok((parts, Trim::default(), raw_tail))
+      }
+      Piece::Part(part, trim) => {
+        if let (true, Some(TemplatePart::Text(prev))) =
+          (trim.before, parts.last_mut())
+        {
+          let literal_len = prev.len() - raw_tail;
+          let trimmed_len = literal_len
+            + prev[literal_len..]
+              .trim_end()
+              .len();
+          prev.truncate(trimmed_len);
+
+          if prev.is_empty() {
+            parts.pop();
+          }
+        }
+
+        match (part, parts.last_mut()) {
+          // `{""}`, e.g., the content of an empty branch
+          (TemplatePart::Text(literal), _) if literal.is_empty() => {}
+          (TemplatePart::Text(literal), Some(TemplatePart::Text(prev))) => {
+            prev.push_str(&literal)
+          }
+          (part, _) => parts.push(part),
+        }
+        Ok((parts, trim, 0))
+      }
+    },
+  )
+  .map(|(parts, ..)| parts)
 }
 
-fn parse_variable(input: &str) -> IResult<&str, VariableRef> {
+fn parse_variable(input: &str) -> IResult<&str, (VariableRef, Trim)> {
   // => escaped text, not variable
   if input.starts_with("{{") {
     return nom::error::Error::new(input, nom::error::ErrorKind::Verify)
@@ -83,17 +397,215 @@ fn parse_variable(input: &str) -> IResult<&str, VariableRef> {
       .pipe(Err);
   }
 
+  match parse_call(input) {
+    Ok((input, (call, trim))) => {
+      return (input, (call.pipe(VariableRef::Call), trim)).pipe(Ok);
+    }
+    Err(e @ nom::Err::Failure(_)) => return Err(e),
+    _ => {}
+  }
+
   let (input, content) =
     delimited(tag("{"), take_until("}"), tag("}")).parse(input)?;
+
+  let (before, content) = match content.strip_prefix('~') {
+    Some(c) => (true, c),
+    _ => (false, content),
+  };
+  let (after, content) = match content.strip_suffix('~') {
+    Some(c) => (true, c),
+    _ => (false, content),
+  };
+  let trim = Trim { before, after };
   let content = content.trim();
 
   match content.strip_prefix('$') {
-    Some(param) => (input, VariableRef::Parameter(param.trim().into())),
-    _ => (input, VariableRef::Variable(content.into())),
+    Some(param) => (input, (VariableRef::Parameter(param.trim().into()), trim)),
+    _ => (input, (VariableRef::Variable(content.into()), trim)),
   }
   .pipe(Ok)
 }
 
+/// Parses a markup tag, e.g., `<link>`, `</link>` or `<br/>`
+///
+/// A tag name starts with an ASCII letter, followed by ASCII letters, digits,
+/// `-`, `_`, `.` or `:`. Anything else (e.g., `<a href="x">` or `a < b`) is
+/// plain text.
+pub(crate) fn parse_markup(input: &str) -> IResult<&str, Markup> {
+  let name = || {
+    verify(
+      take_while1(|c: char| {
+        c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | ':')
+      }),
+      |name: &str| name.starts_with(|c: char| c.is_ascii_alphabetic()),
+    )
+    .map(MiniStr::from)
+  };
+
+  alt((
+    delimited(tag("</"), name(), char('>')).map(Markup::Close),
+    delimited(char('<'), name(), tag("/>")).map(Markup::Standalone),
+    delimited(char('<'), name(), char('>')).map(Markup::Open),
+  ))
+  .parse(input)
+}
+
+/// Parses a translator note, e.g., `{# formal tone #}`
+fn parse_comment(input: &str) -> IResult<&str, &str> {
+  delimited(tag("{#"), take_until("#}"), tag("#}"))
+    .map(str::trim)
+    .parse(input)
+}
+
+/// Parses a string-literal placeable, e.g., `{"{"}`, `{ "\u00A0" }`
+fn parse_literal_placeable(input: &str) -> IResult<&str, (MiniStr, Trim)> {
+  (
+    (tag("{"), opt(char('~')), multispace0),
+    parse_string_literal,
+    (multispace0, opt(char('~')), tag("}")),
+  )
+    .map(|((_, before, _), literal, (_, after, _))| {
+      let trim = Trim {
+        before: before.is_some(),
+        after: after.is_some(),
+      };
+      (literal, trim)
+    })
+    .parse(input)
+}
+
+/// Parses a double-quoted string literal.
+///
+/// ## Escape sequences
+///
+/// - `\\` => `\`
+/// - `\"` => `"`
+/// - `\uHHHH` => Unicode code point (4 hex digits)
+/// - `\UHHHHHH` => Unicode code point (6 hex digits)
+fn parse_string_literal(input: &str) -> IResult<&str, MiniStr> {
+  let (mut input, _) = char('"').parse(input)?;
+  let mut literal = MiniStr::const_new("");
+
+  loop {
+    let (rest, chunk) = take_till(|c| c == '"' || c == '\\').parse(input)?;
+    literal.push_str(chunk);
+
+    let (rest, end) = alt((
+      char('"').map(|_| None),
+      preceded(char('\\'), parse_escaped_char).map(Some),
+    ))
+    .parse(rest)?;
+
+    match end {
+      Some(c) => literal.push(c),
+      _ => return Ok((rest, literal)),
+    }
+    input = rest;
+  }
+}
+
+fn parse_escaped_char(input: &str) -> IResult<&str, char> {
+  let code_point = |n| {
+    map_opt(
+      take_while_m_n(n, n, |c: char| c.is_ascii_hexdigit()),
+      |hex| {
+        u32::from_str_radix(hex, 16)
+          .ok()
+          .and_then(char::from_u32)
+      },
+    )
+  };
+
+  alt((
+    char('\\'),
+    char('"'),
+    preceded(char('u'), code_point(4)),
+    preceded(char('U'), code_point(6)),
+  ))
+  .parse(input)
+}
+
+/// Parses a parameterised reference.
+///
+/// e.g., `{ time-period(period: $when, suffix: "!") }`
+///
+/// Once `name(` is matched, the placeable must be a call: a malformed
+/// argument list is a [nom::Err::Failure] at the bad argument, instead of a
+/// fallback to a reference named with the whole text.
+fn parse_call(input: &str) -> IResult<&str, (Box<Call>, Trim)> {
+  let (input, (_, before)) = (tag("{"), opt(char('~'))).parse(input)?;
+  let (input, name) = take_till(|c| matches!(c, '(' | '{' | '}')).parse(input)?;
+  let name = name.trim();
+
+  if name.is_empty() || name.starts_with('$') || name.contains(char::is_whitespace) {
+    return nom::error::Error::new(input, nom::error::ErrorKind::Verify)
+      .pipe(nom::Err::Error)
+      .pipe(Err);
+  }
+
+  let (input, _) = char('(').parse(input)?;
+  let (input, (args, _, _, _, after, _)) = cut((
+    separated_list0(char(','), parse_call_arg),
+    multispace0,
+    char(')'),
+    multispace0,
+    opt(char('~')),
+    char('}'),
+  ))
+  .parse(input)?;
+
+  let trim = Trim {
+    before: before.is_some(),
+    after: after.is_some(),
+  };
+
+  Call {
+    name: name.into(),
+    args: args.into(),
+  }
+  .pipe(Box::new)
+  .pipe(|call| Ok((input, (call, trim))))
+}
+
+fn parse_call_arg(input: &str) -> IResult<&str, (MiniStr, CallArg)> {
+  let is_ident_char =
+    |c: char| !c.is_whitespace() && !matches!(c, ':' | ',' | '(' | ')' | '"' | '$');
+
+  let parameter = preceded(char('$'), take_while1(is_ident_char)).map(|p: &str| {
+    p.pipe(MiniStr::from)
+      .pipe(CallArg::Parameter)
+  });
+
+  let string_literal = parse_string_literal.map(CallArg::Literal);
+
+  // e.g., `3`, `-1.5`
+  let number_literal = recognize((
+    opt(char('-')),
+    take_while1(|c: char| c.is_ascii_digit()),
+    opt((char('.'), take_while1(|c: char| c.is_ascii_digit()))),
+  ))
+  .map(|n: &str| {
+    n.pipe(MiniStr::from)
+      .pipe(CallArg::Literal)
+  });
+
+  separated_pair(
+    delimited(multispace0, take_while1(is_ident_char), multispace0),
+    char(':'),
+    delimited(
+      multispace0,
+      alt((parameter, string_literal, number_literal)),
+      multispace0,
+    ),
+  )
+  .map(|(name, value): (&str, _)| (name.into(), value))
+  .parse(input)
+}
+
+/// Parses escaped text, e.g., `{{ a }}` => `a`
+///
+/// The content is trimmed for compatibility. Use `{"..."}` for exact
+/// whitespace.
 fn parse_delimited_braces(input: &str) -> IResult<&str, &str> {
   // Count opening braces
   let (input, braces) = take_while(|c| c == '{').parse(input)?;
@@ -123,7 +635,12 @@ fn parse_delimited_braces(input: &str) -> IResult<&str, &str> {
 }
 
 fn parse_text(input: &str) -> IResult<&str, &str> {
-  let (input, content) = take_till(|c| c == '{').parse(input)?;
+  // `<` that does not start a markup tag, e.g., `1 < 2`
+  if let Some(rest) = input.strip_prefix('<') {
+    return Ok((rest, &input[..1]));
+  }
+
+  let (input, content) = take_till(|c| c == '{' || c == '<').parse(input)?;
 
   match [input.starts_with("{{"), content.is_empty()]
     .iter()
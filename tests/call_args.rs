@@ -0,0 +1,81 @@
+use glossa_dsl::{Error, Resolver, error::ResolverResult};
+
+mod common;
+
+fn init_resolver() -> ResolverResult<Resolver> {
+  common::init_resolver_with(&[
+    (
+      "greeting",
+      "{ time-period(period: $when) }, { $name }! { time-period(period: \"morning\") }.",
+    ),
+    ("count", "{ n-items(num: 3, unit: \"apples\") }"),
+    ("n-items", "{$num} {$unit} for {$name}"),
+  ])
+}
+
+#[test]
+fn test_call_with_params_and_literals() -> ResolverResult<()> {
+  let res = init_resolver()?;
+
+  let text =
+    res.get_with_context("greeting", &[("when", "evening"), ("name", "Alice")])?;
+  assert_eq!(text, "Good evening, Alice! Good Morning.");
+
+  // Arguments shadow the caller's context; other parameters stay visible.
+  let text = res.get_with_context("count", &[("num", "1"), ("name", "Tom")])?;
+  assert_eq!(text, "3 apples for Tom");
+  Ok(())
+}
+
+#[test]
+fn test_call_missing_argument_param() -> ResolverResult<()> {
+  let res = init_resolver()?;
+
+  let err = res
+    .get_with_context("greeting", &[("name", "Alice")])
+    .unwrap_err();
+  assert!(matches!(err, Error::MissingParameter(p) if p == "when"));
+  Ok(())
+}
+
+#[test]
+fn test_malformed_call_is_parse_error() {
+  for src in [
+    "{ f(x: $a,) }",
+    "{ f(x: 1-2-3) }",
+    "{ f(x: --1) }",
+    "{ f(x) }",
+    "{ f( }",
+    "{ f(: 1) }",
+    "{ f($x: 1) }",
+    "{ f(x: 1 y: 2) }",
+    "{ f(x: \"a) }",
+    "$n ->\n [a] { f(x: $a,) }\n *[b] b",
+  ] {
+    let err = Resolver::try_from_slice(&[("k", src)]).unwrap_err();
+    assert!(
+      matches!(&err, Error::ParseError(msg) if msg.contains("Invalid call argument")),
+      "{src}: {err}"
+    );
+  }
+
+  let res =
+    Resolver::try_from_slice(&[("neg", "{ f(x: -1.5) }"), ("f", "{$x}")]).unwrap();
+  assert_eq!(res.try_get("neg").unwrap(), "-1.5");
+}
+
+#[test]
+fn test_call_arguments_are_scoped() -> ResolverResult<()> {
+  let res = Resolver::try_from_slice(&[
+    ("k", "{ f(x: \"1\") } { $x } { f() }"),
+    ("f", "{$x}"),
+  ])?;
+
+  // An argument is visible in the called message only, and an empty list
+  // passes the caller's context through.
+  assert_eq!(res.get_with_context("k", &[("x", "2")])?, "1 2 2");
+
+  let err = res.try_get("k").unwrap_err();
+  assert!(matches!(err, Error::MissingParameter(p) if p == "x"));
+  Ok(())
+}
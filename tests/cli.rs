@@ -0,0 +1,325 @@
+#![cfg(feature = "cli")]
+
+use std::{
+  fs,
+  path::PathBuf,
+  process::{Command, Output},
+};
+
+fn glossa(args: &[&str]) -> Output {
+  Command::new(env!("CARGO_BIN_EXE_glossa"))
+    .args(args)
+    .output()
+    .expect("Failed to run glossa")
+}
+
+fn write_tmp(name: &str, content: &str) -> PathBuf {
+  let dir = std::env::temp_dir().join("glossa-dsl-cli-test");
+  fs::create_dir_all(&dir).expect("Failed to create temp dir");
+  let path = dir.join(name);
+  fs::write(&path, content).expect("Failed to write temp file");
+  path
+}
+
+#[test]
+fn test_check_and_render() {
+  let file = write_tmp(
+    "en.toml",
+    r#"
+g = "Good"
+time-period = """
+$period ->
+  [morning] { g } Morning
+  *[other] { g } { $period }
+"""
+greeting = "{ time-period }! { $name }"
+"#,
+  );
+  let file = file.to_str().unwrap();
+
+  let out = glossa(&["check", file]);
+  assert!(out.status.success());
+
+  let out = glossa(&["lint", file]);
+  assert!(
+    out.status.success(),
+    "{}",
+    String::from_utf8_lossy(&out.stderr)
+  );
+
+  let out = glossa(&[
+    "render",
+    file,
+    "greeting",
+    "--param",
+    "period=evening",
+    "--param=name=Alice",
+  ]);
+  assert!(out.status.success());
+  assert_eq!(
+    String::from_utf8_lossy(&out.stdout),
+    "Good evening! Alice\n"
+  );
+}
+
+#[test]
+fn test_check_reports_all_errors() {
+  let file = write_tmp(
+    "broken.json",
+    r#"{
+      "a": "{ b }",
+      "b": "{ a }",
+      "c": "{ missing }",
+      "d": "{{a}"
+    }"#,
+  );
+
+  let out = glossa(&["check", file.to_str().unwrap()]);
+  let stderr = String::from_utf8_lossy(&out.stderr);
+
+  assert_eq!(out.status.code(), Some(1));
+  assert!(stderr.contains("Failed to parse 'd'"), "{stderr}");
+  assert!(stderr.contains("Undefined reference in 'c': missing"));
+  assert!(stderr.contains("Cyclic reference: a -> b -> a"));
+  assert!(stderr.contains("3 error(s)"));
+}
+
+#[test]
+fn test_nested_tables() {
+  let file = write_tmp(
+    "tables.toml",
+    r#"h = "Hello"
+
+[settings]
+title = "{ h } Settings"
+
+[settings.network]
+proxy = "{ settings.title }: Proxy"
+"#,
+  );
+  let file = file.to_str().unwrap();
+
+  let out = glossa(&["lint", file]);
+  assert!(
+    out.status.success(),
+    "{}",
+    String::from_utf8_lossy(&out.stderr)
+  );
+
+  let out = glossa(&["render", file, "settings.network.proxy"]);
+  assert_eq!(
+    String::from_utf8_lossy(&out.stdout),
+    "Hello Settings: Proxy\n"
+  );
+
+  let file = write_tmp("number.toml", "version = 3");
+  let out = glossa(&["check", file.to_str().unwrap()]);
+  let stderr = String::from_utf8_lossy(&out.stderr);
+  assert_eq!(out.status.code(), Some(1));
+  assert!(
+    stderr.contains("'version': expected a string or a table"),
+    "{stderr}"
+  );
+}
+
+#[test]
+fn test_render_ignores_unrelated_errors() {
+  let file = write_tmp(
+    "partly-broken.json",
+    r#"{
+      "h": "Hello",
+      "greeting": "{ h } { $name }",
+      "bad": "{{a}",
+      "uses-bad": "{ bad }",
+      "cyclic": "{ cyclic }"
+    }"#,
+  );
+  let file = file.to_str().unwrap();
+
+  let out = glossa(&["render", file, "greeting", "--param", "name=Tom"]);
+  assert!(
+    out.status.success(),
+    "{}",
+    String::from_utf8_lossy(&out.stderr)
+  );
+  assert_eq!(String::from_utf8_lossy(&out.stdout), "Hello Tom\n");
+
+  let out = glossa(&["render", file, "uses-bad"]);
+  let stderr = String::from_utf8_lossy(&out.stderr);
+  assert_eq!(out.status.code(), Some(1));
+  assert!(stderr.contains("Failed to parse 'bad'"), "{stderr}");
+
+  let out = glossa(&["render", file, "cyclic"]);
+  let stderr = String::from_utf8_lossy(&out.stderr);
+  assert_eq!(out.status.code(), Some(1));
+  assert!(
+    stderr.contains("Cyclic reference: cyclic -> cyclic"),
+    "{stderr}"
+  );
+}
+
+#[test]
+fn test_usage_error() {
+  let out = glossa(&["render", "en.toml"]);
+  assert_eq!(out.status.code(), Some(2));
+}
+
+#[test]
+fn test_compile_and_decompile() {
+  let src = r#"greeting = "{ h }, { $name }!"
+h = """
+$period ->
+  [morning] Good Morning
+  *[other] Hello
+"""
+"#;
+  let file = write_tmp("compile.toml", src);
+  let bin = file.with_extension("bincode");
+  let _ = fs::remove_file(&bin);
+
+  let out = glossa(&["compile", file.to_str().unwrap()]);
+  assert!(out.status.success());
+  assert!(bin.is_file());
+
+  let out = glossa(&["decompile", bin.to_str().unwrap()]);
+  assert!(out.status.success());
+  assert_eq!(String::from_utf8_lossy(&out.stdout), src);
+}
+
+#[test]
+fn test_compile_dir() {
+  let dir = std::env::temp_dir().join("glossa-dsl-cli-test-dir");
+  let out_dir = dir.join("out");
+  let _ = fs::remove_dir_all(&dir);
+  fs::create_dir_all(&dir).unwrap();
+  fs::write(dir.join("en.toml"), r#"hi = "Hello""#).unwrap();
+  fs::write(dir.join("zh.json"), r#"{"hi": "你好"}"#).unwrap();
+
+  let compile = || {
+    glossa(&[
+      "compile",
+      dir.to_str().unwrap(),
+      "-o",
+      out_dir.to_str().unwrap(),
+    ])
+  };
+  let out = compile();
+  assert!(out.status.success());
+  assert!(
+    out_dir
+      .join("en.bincode")
+      .is_file()
+  );
+  assert!(
+    out_dir
+      .join("zh.bincode")
+      .is_file()
+  );
+
+  // A broken locale fails the whole run, and nothing is written.
+  fs::write(dir.join("broken.toml"), r#"hi = "{ missing }""#).unwrap();
+  let _ = fs::remove_dir_all(&out_dir);
+  let out = compile();
+  let stderr = String::from_utf8_lossy(&out.stderr);
+  assert_eq!(out.status.code(), Some(1));
+  assert!(stderr.contains("missing"), "{stderr}");
+  assert!(!out_dir.exists());
+}
+
+#[test]
+fn test_compile_locale_dirs() {
+  let dir = std::env::temp_dir().join("glossa-dsl-cli-test-locales");
+  let out_dir = dir.join("out");
+  let _ = fs::remove_dir_all(&dir);
+  for (lang, app, title) in [
+    ("en", "Glossa", "{ app } Settings"),
+    ("zh", "Glossa", "{ app } 设置"),
+  ] {
+    fs::create_dir_all(dir.join(lang)).unwrap();
+    // `title` references a message of another file of the same locale.
+    fs::write(
+      dir.join(lang).join("main.toml"),
+      format!("title = \"{title}\""),
+    )
+    .unwrap();
+    fs::write(
+      dir
+        .join(lang)
+        .join("common.toml"),
+      format!("app = \"{app}\""),
+    )
+    .unwrap();
+  }
+
+  let compile = || {
+    glossa(&[
+      "compile",
+      dir.to_str().unwrap(),
+      "-o",
+      out_dir.to_str().unwrap(),
+    ])
+  };
+  let out = compile();
+  assert!(
+    out.status.success(),
+    "{}",
+    String::from_utf8_lossy(&out.stderr)
+  );
+
+  for (lang, title) in [("en", "{ app } Settings"), ("zh", "{ app } 设置")] {
+    let bin = out_dir.join(format!("{lang}.bincode"));
+    let out = glossa(&["decompile", bin.to_str().unwrap()]);
+    assert_eq!(
+      String::from_utf8_lossy(&out.stdout),
+      format!("app = \"Glossa\"\ntitle = \"{title}\"\n")
+    );
+  }
+
+  // The same key in two files of a locale
+  fs::write(
+    dir
+      .join("zh")
+      .join("extra.json"),
+    r#"{"app": "词汇"}"#,
+  )
+  .unwrap();
+  let _ = fs::remove_dir_all(&out_dir);
+  let out = compile();
+  let stderr = String::from_utf8_lossy(&out.stderr);
+  assert_eq!(out.status.code(), Some(1));
+  assert!(stderr.contains("extra.json"), "{stderr}");
+  assert!(!out_dir.exists());
+}
+
+#[test]
+fn test_check_dir() {
+  let dir = std::env::temp_dir().join("glossa-dsl-cli-test-check-dir");
+  let _ = fs::remove_dir_all(&dir);
+  fs::create_dir_all(dir.join("en")).unwrap();
+  fs::write(
+    dir.join("en").join("main.toml"),
+    "title = \"{ app } Settings\"\n",
+  )
+  .unwrap();
+  fs::write(
+    dir
+      .join("en")
+      .join("common.toml"),
+    "app = \"Glossa\"\n",
+  )
+  .unwrap();
+
+  for cmd in ["check", "lint"] {
+    let out = glossa(&[cmd, dir.to_str().unwrap()]);
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(out.status.success(), "{stderr}");
+    assert!(stderr.contains("2 file(s) checked, no errors"), "{stderr}");
+  }
+
+  fs::write(dir.join("de.toml"), r#"title = "{ app } Einstellungen""#).unwrap();
+  let out = glossa(&["check", dir.to_str().unwrap()]);
+  let stderr = String::from_utf8_lossy(&out.stderr);
+  assert_eq!(out.status.code(), Some(1));
+  assert!(stderr.contains("app"), "{stderr}");
+  assert!(stderr.contains("3 file(s) checked, 1 error(s)"), "{stderr}");
+}
@@ -0,0 +1,80 @@
+use glossa_dsl::{Resolver, error::ResolverResult};
+
+#[test]
+fn test_comments_are_stripped_but_kept() -> ResolverResult<()> {
+  let res: Resolver = [
+    (
+      "unread",
+      "
+      # Inbox header. Keep it short.
+      $num ->
+        # `num` is never negative
+        [0] No messages.
+        [1] One message.{# singular #}
+        *[other] {$num} messages.
+      ",
+    ),
+    ("title", "{# page title #}# Inbox {$user}"),
+  ]
+  .try_into()?;
+
+  assert_eq!(
+    res.get_with_context("unread", &[("num", "1")])?,
+    "One message."
+  );
+  assert_eq!(
+    res.get_with_context("unread", &[("num", "9")])?,
+    "9 messages."
+  );
+  assert_eq!(
+    res["unread"].comments(),
+    [
+      "Inbox header. Keep it short.",
+      "`num` is never negative",
+      "singular"
+    ]
+  );
+
+  // `#` in plain text is not a comment
+  assert_eq!(
+    res.get_with_context("title", &[("user", "Tom")])?,
+    "# Inbox Tom"
+  );
+  assert_eq!(res["title"].comments(), ["page title"]);
+  Ok(())
+}
+
+#[test]
+fn test_comments_stay_with_their_branch() -> ResolverResult<()> {
+  let res: Resolver = [(
+    "unread",
+    "# header
+    $num ->
+      # default
+      *[other] {$num} messages.
+      # zero
+      [0] No messages.
+      # trailing",
+  )]
+  .try_into()?;
+
+  let source = res["unread"].to_source()?;
+  assert_eq!(
+    source,
+    "# header
+$num ->
+  # zero
+  [0] No messages.
+  # default
+  *[other] { $num } messages.
+  # trailing"
+  );
+  assert_eq!(
+    res["unread"].comments(),
+    ["header", "zero", "default", "trailing"]
+  );
+
+  let reparsed: Resolver = [("unread", source.as_str())].try_into()?;
+  assert_eq!(reparsed["unread"], res["unread"]);
+  Ok(())
+}
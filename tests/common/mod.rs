@@ -0,0 +1,32 @@
+//! Fixture shared by the integration tests.
+
+use glossa_dsl::{Resolver, error::ResolverResult};
+use tap::Pipe;
+
+/// `greeting` calls `time-period`, which selects on `$period` and references
+/// `g`.
+const BASE: [(&str, &str); 3] = [
+  ("g", "Good"),
+  (
+    "time-period",
+    "$period ->
+        [morning] {g} Morning
+        *[other] {g} {$period}",
+  ),
+  ("greeting", "{ time-period(period: $when) }, { $name }!"),
+];
+
+/// Builds a [Resolver] from the shared messages plus `extra`, where an entry
+/// of `extra` replaces the shared message with the same key.
+pub fn init_resolver_with(extra: &[(&str, &str)]) -> ResolverResult<Resolver> {
+  BASE
+    .into_iter()
+    .filter(|(key, _)| {
+      !extra
+        .iter()
+        .any(|(k, _)| k == key)
+    })
+    .chain(extra.iter().copied())
+    .collect::<Vec<_>>()
+    .pipe_deref(Resolver::try_from_slice)
+}
@@ -0,0 +1,64 @@
+use glossa_dsl::{Resolver, error::ResolverResult, resolver::ParameterMismatch};
+
+mod common;
+
+fn en() -> ResolverResult<Resolver> {
+  common::init_resolver_with(&[("apples", "You have {$num} apples")])
+}
+
+#[test]
+fn test_consistent_translation() -> ResolverResult<()> {
+  let de: Resolver = [
+    ("g", "Guten"),
+    (
+      "time-period",
+      "$period ->
+        [morning] {g} Morgen
+        *[other] {g} {$period}",
+    ),
+    ("greeting", "{ $name }, { time-period(period: $when) }!"),
+    ("apples", "Du hast {$num} Äpfel"),
+  ]
+  .try_into()?;
+
+  let en = en()?;
+  let report = en.check_consistency(&de);
+  assert!(report.is_consistent(), "{report:?}");
+  Ok(())
+}
+
+#[test]
+fn test_dropped_and_renamed_parameters() -> ResolverResult<()> {
+  let zh: Resolver = [
+    ("g", "好"),
+    ("time-period", "{g}"),
+    ("greeting", "{ time-period(period: $time) }，{ $name }！"),
+    ("apples", "你有一些苹果"),
+  ]
+  .try_into()?;
+
+  let en = en()?;
+  let report = en.check_consistency(&zh);
+  assert!(report.missing.is_empty() && report.extra.is_empty());
+  assert_eq!(
+    report.mismatched,
+    [
+      ParameterMismatch {
+        key: "apples",
+        missing: vec!["num"],
+        extra: vec![],
+      },
+      ParameterMismatch {
+        key: "greeting",
+        missing: vec!["when"],
+        extra: vec!["time"],
+      },
+      ParameterMismatch {
+        key: "time-period",
+        missing: vec!["period"],
+        extra: vec![],
+      },
+    ]
+  );
+  Ok(())
+}
@@ -0,0 +1,89 @@
+#![cfg(feature = "formatter")]
+
+use glossa_dsl::{
+  Error, Resolver,
+  formatter::{format_toml, is_toml_formatted},
+};
+use tap::Pipe;
+
+const SRC: &str = r#"
+# Numbers
+[numbers]
+  num-to-en = """
+    $num ->
+      [0] zero
+        # more to come
+     *[other] {$num}
+  """
+
+[messages]
+unread_msg = "unread message" # singular
+unread-count = """
+
+  $num ->
+    [0] No {unread_msg}s.
+    *[other] You have { num-to-en } {unread_msg}s.
+"""
+# never translated
+version = 3
+"#;
+
+#[test]
+fn test_format_toml() -> anyhow::Result<()> {
+  let formatted = format_toml(SRC)?;
+
+  assert_eq!(
+    formatted,
+    r#"
+# Numbers
+[numbers]
+  num-to-en = """
+$num ->
+  [0] zero
+  # more to come
+  *[other] { $num }
+"""
+
+[messages]
+unread_msg = "unread message" # singular
+unread-count = """
+$num ->
+  [0] No { unread_msg }s.
+  *[other] You have { num-to-en } { unread_msg }s.
+"""
+# never translated
+version = 3
+"#
+  );
+  assert!(is_toml_formatted(&formatted)?);
+  assert!(!is_toml_formatted(SRC)?);
+  Ok(())
+}
+
+#[test]
+fn test_formatted_messages_are_unchanged() -> anyhow::Result<()> {
+  let to_resolver = |src: &str| -> anyhow::Result<Resolver> {
+    src
+      .pipe(toml::from_str::<toml::Table>)?
+      .values()
+      .filter_map(|v| v.as_table())
+      .flatten()
+      .filter_map(|(k, v)| Some((k.clone(), v.as_str()?.to_owned())))
+      .pipe(Resolver::try_from_raw)?
+      .pipe(Ok)
+  };
+
+  assert_eq!(to_resolver(SRC)?, to_resolver(&format_toml(SRC)?)?);
+  Ok(())
+}
+
+#[test]
+fn test_format_never_changes_a_message() -> anyhow::Result<()> {
+  let src = concat!(r#"c = '{ f(x: "a\\b \"q\"") }'"#, "\n");
+  assert_eq!(format_toml(src)?, src);
+
+  // A reference named `"a` would be printed as the start of a string literal.
+  let err = format_toml(r#"c = '{"a}{""}"}'"#).unwrap_err();
+  assert!(matches!(err, Error::SourceError(msg) if msg.contains("'c'")));
+  Ok(())
+}
@@ -0,0 +1,131 @@
+#![cfg(feature = "loader")]
+
+use std::{fs, path::PathBuf};
+
+use glossa_dsl::{Error, error::ResolverResult, loader::load_locales_dir};
+
+fn init_dir(name: &str, files: &[(&str, &str)]) -> PathBuf {
+  let dir = std::env::temp_dir()
+    .join("glossa-dsl-loader-test")
+    .join(name);
+  let _ = fs::remove_dir_all(&dir);
+
+  for (file, content) in files {
+    let path = dir.join(file);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, content).unwrap();
+  }
+  dir
+}
+
+#[test]
+fn test_load_locales_dir() -> ResolverResult<()> {
+  let dir = init_dir(
+    "ok",
+    &[
+      ("en/main.toml", r#"greeting = "{ h } { $name }""#),
+      ("en/common/h.json", r#"{"h": "Hello"}"#),
+      ("en/notes.txt", "ignored"),
+      ("zh/main.toml", r#"greeting = "你好 { $name }""#),
+      ("de.toml", r#"greeting = "Hallo { $name }""#),
+      ("ar.toml", r#"greeting = "مرحبا { $name }""#),
+    ],
+  );
+
+  let locales = load_locales_dir(dir)?;
+  assert_eq!(
+    locales
+      .keys()
+      .map(|k| k.as_str())
+      .collect::<Vec<_>>(),
+    ["ar", "de", "en", "zh"]
+  );
+
+  let ctx = [("name", "Tom")];
+  assert_eq!(
+    locales["en"].get_with_context("greeting", &ctx)?,
+    "Hello Tom"
+  );
+  assert_eq!(
+    locales["zh"].get_with_context("greeting", &ctx)?,
+    "你好 Tom"
+  );
+  assert_eq!(
+    locales["de"].get_with_context("greeting", &ctx)?,
+    "Hallo Tom"
+  );
+
+  // Right-to-left locales isolate parameters by default.
+  assert_eq!(
+    locales["ar"].get_with_context("greeting", &ctx)?,
+    "مرحبا \u{2068}Tom\u{2069}"
+  );
+  Ok(())
+}
+
+#[test]
+fn test_duplicate_key() {
+  let dir = init_dir(
+    "dup",
+    &[
+      ("en/a.toml", r#"h = "Hello""#),
+      ("en/b.json", r#"{"h": "Hi"}"#),
+    ],
+  );
+
+  let err = load_locales_dir(&dir).unwrap_err();
+  assert!(
+    matches!(&err, Error::DuplicateKey(key, first, second)
+      if key == "h" && first.ends_with("en/a.toml") && second.ends_with("en/b.json")),
+    "{err}"
+  );
+}
+
+#[test]
+fn test_nested_tables_are_flattened() -> ResolverResult<()> {
+  let dir = init_dir(
+    "tables",
+    &[
+      ("en/main.toml", "[settings]\ntitle = \"Settings\""),
+      ("en/brand.json", r#"{"-brand": {"gender": "masculine"}}"#),
+      ("zh/main.toml", "\"a.b\" = \"1\"\n[a]\nb = \"2\""),
+    ],
+  );
+
+  let err = load_locales_dir(&dir).unwrap_err();
+  assert!(
+    matches!(&err, Error::InFile(path, e)
+      if path.ends_with("zh/main.toml") && matches!(**e, Error::ConflictingKey(ref k) if k == "a.b")),
+    "{err}"
+  );
+
+  fs::remove_dir_all(dir.join("zh")).unwrap();
+  let locales = load_locales_dir(&dir)?;
+  assert_eq!(locales["en"].try_get("settings.title")?, "Settings");
+  assert_eq!(locales["en"].try_get("-brand.gender")?, "masculine");
+  Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn test_symlink_loop_is_not_followed() -> ResolverResult<()> {
+  let dir = init_dir(
+    "symlink",
+    &[
+      ("en/main.toml", r#"h = "Hello""#),
+      ("shared/extra.toml", r#"x = "Extra""#),
+    ],
+  );
+  std::os::unix::fs::symlink(&dir, dir.join("en/loop")).unwrap();
+  std::os::unix::fs::symlink(
+    dir.join("shared/extra.toml"),
+    dir.join("en/extra.toml"),
+  )
+  .unwrap();
+
+  // The directory link is skipped; the file link is loaded.
+  let locales = load_locales_dir(&dir)?;
+  assert_eq!(locales["en"].try_get("h")?, "Hello");
+  assert_eq!(locales["en"].try_get("x")?, "Extra");
+  Ok(())
+}
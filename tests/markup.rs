@@ -0,0 +1,95 @@
+use glossa_dsl::{
+  Resolver,
+  error::ResolverResult,
+  resolver::{Node, ResolveOptions},
+};
+
+fn text(s: &str) -> Node {
+  Node::Text(s.into())
+}
+
+fn element(name: &str, children: Vec<Node>) -> Node {
+  Node::Element {
+    name: name.into(),
+    children,
+  }
+}
+
+#[test]
+fn test_plain_angle_brackets() -> ResolverResult<()> {
+  let res: Resolver = [
+    ("cmp", "1 < 2 <3 <a href=\"x\">"),
+    ("literal", r#"{"<b>"} bold"#),
+  ]
+  .try_into()?;
+
+  let tree = res.resolve_tree("cmp", &[], &ResolveOptions::default())?;
+  assert_eq!(tree, [text("1 < 2 <3 <a href=\"x\">")]);
+
+  // A string literal is never markup, and is printed as a literal.
+  let tree = res.resolve_tree("literal", &[], &ResolveOptions::default())?;
+  assert_eq!(tree, [text("<b> bold")]);
+  assert_eq!(res["literal"].to_source()?, r#"{"<b> bold"}"#);
+  Ok(())
+}
+
+#[test]
+fn test_unbalanced_tags() -> ResolverResult<()> {
+  let res: Resolver = [
+    ("unclosed", "<b>bold <i>both</b> plain"),
+    ("stray", "a</i> <em>b"),
+  ]
+  .try_into()?;
+
+  // Flat rendering keeps the source as it is.
+  assert_eq!(res.try_get("unclosed")?, "<b>bold <i>both</b> plain");
+
+  let tree = res.resolve_tree("unclosed", &[], &ResolveOptions::default())?;
+  assert_eq!(
+    tree,
+    [
+      element("b", vec![text("bold "), element("i", vec![text("both")])]),
+      text(" plain")
+    ]
+  );
+
+  let tree = res.resolve_tree("stray", &[], &ResolveOptions::default())?;
+  assert_eq!(tree, [text("a</i> "), element("em", vec![text("b")])]);
+  Ok(())
+}
+
+#[test]
+fn test_markup_across_branches_and_source() -> ResolverResult<()> {
+  let res: Resolver = [(
+    "apples",
+    "$num ->
+        [1] <b>one</b> apple
+        *[other] <b>{$num}</b> apples",
+  )]
+  .try_into()?;
+
+  let tree =
+    res.resolve_tree("apples", &[("num", "3")], &ResolveOptions::default())?;
+  assert_eq!(
+    tree,
+    [
+      element(
+        "b",
+        vec![Node::Parameter {
+          name: "num".into(),
+          value: "3".into()
+        }]
+      ),
+      text(" apples")
+    ]
+  );
+
+  let source = res["apples"].to_source()?;
+  assert_eq!(
+    source,
+    "$num ->\n  [1] <b>one</b> apple\n  *[other] <b>{ $num }</b> apples"
+  );
+  let reparsed: Resolver = [("apples", source.as_str())].try_into()?;
+  assert_eq!(reparsed["apples"], res["apples"]);
+  Ok(())
+}
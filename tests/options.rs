@@ -0,0 +1,378 @@
+use glossa_dsl::{
+  Error, Resolver,
+  error::ResolverResult,
+  escape::HtmlEscaper,
+  resolver::{
+    BidiIsolation, EscapeMode, MissingKeyPolicy, MissingParameterPolicy,
+    ResolveOptions, is_rtl_locale,
+  },
+};
+
+mod common;
+
+fn init_resolver() -> ResolverResult<Resolver> {
+  common::init_resolver_with(&[("no-default", "$num ->\n [1] one")])
+}
+
+fn options(policy: MissingParameterPolicy) -> ResolveOptions {
+  ResolveOptions {
+    missing_parameter: policy,
+    ..Default::default()
+  }
+}
+
+#[test]
+fn test_missing_parameter_error() -> ResolverResult<()> {
+  let res = init_resolver()?;
+  let err = res
+    .resolve("greeting", &[("name", "Alice")], &ResolveOptions::default())
+    .unwrap_err();
+  assert!(matches!(err, Error::MissingParameter(p) if p == "when"));
+  Ok(())
+}
+
+#[test]
+fn test_missing_parameter_substitutes() -> ResolverResult<()> {
+  let res = init_resolver()?;
+
+  // The substitute is forwarded to the call, and matches the default branch.
+  let resolved =
+    res.resolve("greeting", &[], &options(MissingParameterPolicy::Literal))?;
+  assert_eq!(resolved.text, "Good {$when}, {$name}!");
+  assert_eq!(resolved.missing_parameters, ["when", "name"]);
+
+  let resolved =
+    res.resolve("greeting", &[], &options(MissingParameterPolicy::Empty))?;
+  assert_eq!(resolved.text, "Good , !");
+
+  let morning = |_: &str| "morning".into();
+  let resolved = res.resolve(
+    "time-period",
+    &[],
+    &options(MissingParameterPolicy::Callback(&morning)),
+  )?;
+  assert_eq!(resolved.text, "Good Morning");
+  assert_eq!(resolved.missing_parameters, ["period"]);
+  Ok(())
+}
+
+#[test]
+fn test_substitute_without_default_branch() -> ResolverResult<()> {
+  let res = init_resolver()?;
+  let err = res
+    .resolve("no-default", &[], &options(MissingParameterPolicy::Empty))
+    .unwrap_err();
+  assert!(matches!(err, Error::NoDefaultBranch(p) if p == "num"));
+  Ok(())
+}
+
+#[test]
+fn test_missing_key_policies() -> ResolverResult<()> {
+  let zh: Resolver = [
+    ("g", "早"),
+    (
+      "greeting",
+      "{ time-period(period: $when) }，{ $name }！{ bye }",
+    ),
+  ]
+  .try_into()?;
+  let en = init_resolver()?;
+  let ctx = [("name", "Alice"), ("when", "evening")];
+
+  let resolve = |policy| {
+    let options = ResolveOptions {
+      missing_key: policy,
+      ..Default::default()
+    };
+    zh.resolve("greeting", &ctx, &options)
+  };
+
+  let err = resolve(MissingKeyPolicy::Error).unwrap_err();
+  assert!(matches!(err, Error::UndefinedVariable(k) if k == "time-period"));
+
+  let resolved = resolve(MissingKeyPolicy::Key)?;
+  assert_eq!(resolved.text, "time-period，Alice！bye");
+  assert_eq!(resolved.missing_keys, ["time-period", "bye"]);
+
+  assert_eq!(
+    resolve(MissingKeyPolicy::Placeholder)?.text,
+    "⟦time-period⟧，Alice！⟦bye⟧"
+  );
+
+  // `bye` is in neither resolver.
+  let err = resolve(MissingKeyPolicy::Fallback(&en)).unwrap_err();
+  assert!(matches!(err, Error::UndefinedVariable(k) if k == "bye"));
+
+  // `{g}` of the fallback message is found in `zh` first.
+  let options = ResolveOptions {
+    missing_key: MissingKeyPolicy::Fallback(&en),
+    ..Default::default()
+  };
+  let resolved = zh.resolve("time-period", &[("period", "morning")], &options)?;
+  assert_eq!(resolved.text, "早 Morning");
+  assert_eq!(resolved.missing_keys, ["time-period"]);
+  Ok(())
+}
+
+#[test]
+fn test_resolver_missing_key_policy() -> ResolverResult<()> {
+  let mut zh: Resolver = [
+    ("g", "早"),
+    ("greeting", "{ time-period(period: $when) }，{ $name }！"),
+  ]
+  .try_into()?;
+  let ctx = [("name", "Alice"), ("when", "morning")];
+
+  let err = zh
+    .get_with_context("greeting", &ctx)
+    .unwrap_err();
+  assert!(matches!(err, Error::UndefinedVariable(k) if k == "time-period"));
+
+  zh.set_missing_key_policy(MissingKeyPolicy::Key);
+  assert_eq!(
+    zh.get_with_context("greeting", &ctx)?,
+    "time-period，Alice！"
+  );
+  assert_eq!(zh.try_get("bye")?, "bye");
+
+  let en: &'static Resolver = Box::leak(Box::new(init_resolver()?));
+  let zh = zh.with_missing_key_policy(MissingKeyPolicy::Fallback(en));
+  assert_eq!(
+    zh.get_with_context("greeting", &ctx)?,
+    "早 Morning，Alice！"
+  );
+  assert!(zh.try_get("bye").is_err());
+
+  // Explicit options take precedence over the policy of the resolver.
+  let err = zh
+    .resolve("greeting", &ctx, &Default::default())
+    .unwrap_err();
+  assert!(matches!(err, Error::UndefinedVariable(k) if k == "time-period"));
+  Ok(())
+}
+
+#[test]
+fn test_resolve_lossy() -> ResolverResult<()> {
+  let res = init_resolver()?;
+
+  let (resolved, errors) =
+    res.resolve_lossy("greeting", &[("when", "morning")], &Default::default());
+  assert_eq!(resolved.text, "Good Morning, {$name}!");
+  assert!(matches!(errors.as_slice(), [Error::MissingParameter(p)] if p == "name"));
+
+  // Policies still apply, and only the remaining problems are errors.
+  let options = options(MissingParameterPolicy::Empty);
+  let (resolved, errors) = res.resolve_lossy("no-default", &[], &options);
+  assert_eq!(resolved.text, "");
+  assert_eq!(resolved.missing_parameters, ["num"]);
+  assert!(matches!(errors.as_slice(), [Error::NoDefaultBranch(p)] if p == "num"));
+
+  let (resolved, errors) = res.resolve_lossy("undefined", &[], &options);
+  assert_eq!(resolved.text, "{undefined}");
+  assert_eq!(errors.len(), 1);
+
+  let ok = res.resolve_lossy("g", &[], &Default::default());
+  assert_eq!(ok.0.text, "Good");
+  assert!(ok.1.is_empty());
+  Ok(())
+}
+
+#[test]
+fn test_cyclic_reference() -> ResolverResult<()> {
+  let res: Resolver =
+    [("a", "A{ b }"), ("b", "B{ a }"), ("c", "{ b }, { b }")].try_into()?;
+
+  let (resolved, errors) = res.resolve_lossy("a", &[], &Default::default());
+  assert_eq!(resolved.text, "AB{a}");
+  assert!(
+    matches!(errors.as_slice(), [Error::CyclicReference(path)] if path == "a -> b -> a")
+  );
+
+  // The same message may be referenced twice, as long as it is not nested.
+  let (resolved, errors) = res.resolve_lossy("c", &[], &Default::default());
+  assert_eq!(resolved.text, "BA{b}, BA{b}");
+  assert_eq!(errors.len(), 2);
+
+  let err = res.try_get("a").unwrap_err();
+  assert!(matches!(err, Error::CyclicReference(path) if path == "a -> b -> a"));
+  Ok(())
+}
+
+#[test]
+fn test_bidi_isolation() -> ResolverResult<()> {
+  let res = init_resolver()?;
+  let ctx = [("name", "Alice"), ("when", "evening")];
+
+  let mut options = ResolveOptions::for_locale("he-IL");
+  assert_eq!(options.bidi_isolation, BidiIsolation::Parameters);
+  assert_eq!(
+    res
+      .resolve("greeting", &ctx, &options)?
+      .text,
+    "Good \u{2068}evening\u{2069}, \u{2068}Alice\u{2069}!"
+  );
+
+  options.bidi_isolation = BidiIsolation::ParametersAndReferences;
+  assert_eq!(
+    res
+      .resolve("greeting", &ctx, &options)?
+      .text,
+    "\u{2068}\u{2068}Good\u{2069} \u{2068}evening\u{2069}\u{2069}, \u{2068}Alice\u{2069}!"
+  );
+
+  let options = ResolveOptions::for_locale("en");
+  assert_eq!(
+    res
+      .resolve("greeting", &ctx, &options)?
+      .text,
+    "Good evening, Alice!"
+  );
+  Ok(())
+}
+
+#[test]
+fn test_resolver_bidi_isolation() -> ResolverResult<()> {
+  let mut res = init_resolver()?;
+  let ctx = [("name", "Alice"), ("when", "evening")];
+  assert_eq!(res.bidi_isolation(), BidiIsolation::Off);
+  assert_eq!(
+    res.get_with_context("greeting", &ctx)?,
+    "Good evening, Alice!"
+  );
+
+  res.set_bidi_isolation(BidiIsolation::for_locale("ar"));
+  assert_eq!(res.bidi_isolation(), BidiIsolation::Parameters);
+  assert_eq!(
+    res.get_with_context("greeting", &ctx)?,
+    "Good \u{2068}evening\u{2069}, \u{2068}Alice\u{2069}!"
+  );
+  assert_eq!(res.try_get("g")?, "Good");
+
+  let map = ctx
+    .iter()
+    .map(|&(k, v)| (k.into(), v.into()))
+    .collect();
+  assert_eq!(
+    res.get_with_ctx_btree_map("greeting", &map)?,
+    "Good \u{2068}evening\u{2069}, \u{2068}Alice\u{2069}!"
+  );
+
+  // Explicit options take precedence over the default of the resolver.
+  assert_eq!(
+    res
+      .resolve("greeting", &ctx, &Default::default())?
+      .text,
+    "Good evening, Alice!"
+  );
+
+  // Settings are not compared.
+  assert_eq!(res, init_resolver()?);
+  assert_eq!(Resolver::new(res.clone().into_inner()), res);
+
+  let res = res.with_bidi_isolation(BidiIsolation::ParametersAndReferences);
+  assert_eq!(
+    res.get_with_context("greeting", &ctx)?,
+    "\u{2068}\u{2068}Good\u{2069} \u{2068}evening\u{2069}\u{2069}, \u{2068}Alice\u{2069}!"
+  );
+  Ok(())
+}
+
+#[test]
+fn test_rtl_locales() {
+  ["ar", "fa_IR", "he", "ur-PK", "pa-Arab", "ckb"]
+    .into_iter()
+    .for_each(|tag| assert!(is_rtl_locale(tag), "{tag}"));
+
+  ["en", "zh-Hans-CN", "ku-Latn", "az-Latn-AZ", "pa", ""]
+    .into_iter()
+    .for_each(|tag| assert!(!is_rtl_locale(tag), "{tag}"));
+}
+
+#[test]
+fn test_escape_mode() -> ResolverResult<()> {
+  let res: Resolver = [
+    ("brand", "Tom & Jerry"),
+    ("title", "<i>{ brand }</i> by {$author}"),
+  ]
+  .try_into()?;
+  let ctx = [("author", "<b>Hanna</b>")];
+
+  let mut options = ResolveOptions {
+    escape: EscapeMode::Parameters(&HtmlEscaper),
+    ..Default::default()
+  };
+  assert_eq!(
+    res
+      .resolve("title", &ctx, &options)?
+      .text,
+    "<i>Tom & Jerry</i> by &lt;b&gt;Hanna&lt;/b&gt;"
+  );
+
+  // References are escaped once, by their own text.
+  options.escape = EscapeMode::Text(&HtmlEscaper);
+  assert_eq!(
+    res
+      .resolve("title", &ctx, &options)?
+      .text,
+    "&lt;i&gt;Tom &amp; Jerry&lt;/i&gt; by <b>Hanna</b>"
+  );
+
+  // Any closure can be an escaper.
+  let upper = |input: &str, output: &mut glossa_dsl::resolver::MiniStr| {
+    output.push_str(&input.to_uppercase())
+  };
+  options.escape = EscapeMode::Parameters(&upper);
+  assert_eq!(
+    res
+      .resolve("title", &[("author", "hanna")], &options)?
+      .text,
+    "<i>Tom & Jerry</i> by HANNA"
+  );
+  Ok(())
+}
+
+#[test]
+fn test_escape_substitutes() -> ResolverResult<()> {
+  let res: Resolver =
+    [("title", "<i>{ <brand> }</i>"), ("loop", "<b>{ loop }</b>")].try_into()?;
+
+  let mut options = ResolveOptions {
+    missing_key: MissingKeyPolicy::Key,
+    escape: EscapeMode::Text(&HtmlEscaper),
+    ..Default::default()
+  };
+  assert_eq!(
+    res
+      .resolve("title", &[], &options)?
+      .text,
+    "&lt;i&gt;&lt;brand&gt;&lt;/i&gt;"
+  );
+
+  options.missing_key = MissingKeyPolicy::Placeholder;
+  assert_eq!(
+    res
+      .resolve("title", &[], &options)?
+      .text,
+    "&lt;i&gt;⟦&lt;brand&gt;⟧&lt;/i&gt;"
+  );
+
+  // Substitutes of lossy errors are escaped in the same way.
+  options.missing_key = MissingKeyPolicy::Error;
+  let (resolved, _) = res.resolve_lossy("title", &[], &options);
+  assert_eq!(resolved.text, "&lt;i&gt;{&lt;brand&gt;}&lt;/i&gt;");
+
+  let (resolved, errors) = res.resolve_lossy("loop", &[], &options);
+  assert_eq!(resolved.text, "&lt;b&gt;{loop}&lt;/b&gt;");
+  assert!(matches!(errors[..], [Error::CyclicReference(_)]));
+
+  // Parameter escaping does not touch the substitutes.
+  options.escape = EscapeMode::Parameters(&HtmlEscaper);
+  options.missing_key = MissingKeyPolicy::Key;
+  assert_eq!(
+    res
+      .resolve("title", &[], &options)?
+      .text,
+    "<i><brand></i>"
+  );
+  Ok(())
+}
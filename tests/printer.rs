@@ -0,0 +1,138 @@
+use glossa_dsl::{Resolver, error::ResolverResult};
+use tap::Pipe;
+
+const SOURCES: &[(&str, &str)] = &[
+  ("h", "Hello"),
+  ("greeting", "{h}  { $name }! Today is {$day}.\n"),
+  ("escaped", "{h}!{{ how_are_you }}? {{    {$name} }}"),
+  ("literal", r#"{""}{"  "}a{"{"}b{" \"\\"}"#),
+  ("dollar", "$5 off"),
+  ("hash", "# Title"),
+  ("comment", "{# note #}{ $a }"),
+  ("call", r#"{ greeting(name: $who, day: "Monday", n: 3) }"#),
+  (
+    "selector",
+    r#"
+    # Inbox header
+    $num ->
+      *[其他] You have {$num} messages.
+      # zero
+      [0] {"  "}No messages.{# short #}
+      [1] { one(x: $num) }
+      [2]"#,
+  ),
+  ("multiline", "line 1\n  line 2\r\n{{ a }}"),
+  ("call_escape", r#"{ f(x: "a\\b", y: "\"", z: "\u0007") }"#),
+  (
+    "nested",
+    "$a ->
+      [x] $b -> [1] one
+      [y] $b -> # never shown
+      *[z] $b -> *[other] {$b}",
+  ),
+  ("trailing", "$a ->\n [x] x\n *[] {$a} {\"\"}"),
+];
+
+#[test]
+fn test_print_then_parse_is_identity() -> ResolverResult<()> {
+  let res = Resolver::try_from_slice(SOURCES)?;
+
+  for (key, tmpl) in res.iter() {
+    let source = tmpl.to_source()?;
+    let reparsed = [(key.as_str(), source.as_str())]
+      .as_ref()
+      .pipe(Resolver::try_from_slice)?;
+
+    assert_eq!(reparsed[key.as_str()], *tmpl, "{key}: {source}");
+    // canonical output is stable
+    assert_eq!(reparsed[key.as_str()].to_source()?, source);
+  }
+  Ok(())
+}
+
+#[test]
+fn test_canonical_source() -> ResolverResult<()> {
+  let res = Resolver::try_from_slice(SOURCES)?;
+
+  assert_eq!(
+    res["greeting"].to_string(),
+    "{ h }  { $name }! Today is { $day }.\n"
+  );
+  assert_eq!(res["literal"].to_string(), r#"{"  a{b \"\\"}"#);
+  assert_eq!(res["dollar"].to_string(), r#"{"$5 off"}"#);
+  assert_eq!(
+    res["selector"].to_string(),
+    r#"# Inbox header
+$num ->
+  # zero
+  [0] {"  No messages."}{# short #}
+  [1] { one(x: $num) }
+  [2] {""}
+  *[其他] You have { $num } messages."#
+  );
+  Ok(())
+}
+
+#[test]
+fn test_escaped_call_arguments() -> ResolverResult<()> {
+  let res = Resolver::try_from_slice(SOURCES)?;
+
+  assert_eq!(
+    res["call_escape"].to_source()?,
+    r#"{ f(x: "a\\b", y: "\"", z: "\u0007") }"#
+  );
+  Ok(())
+}
+
+#[test]
+fn test_nested_selectors() -> ResolverResult<()> {
+  let res = Resolver::try_from_slice(SOURCES)?;
+
+  assert_eq!(
+    res["nested"].to_source()?,
+    "$a ->
+  [x] $b -> [1] one
+  [y] $b -> # never shown
+  *[z] $b -> *[other] { $b }"
+  );
+  Ok(())
+}
+
+#[test]
+fn test_trailing_space_of_last_branch() -> ResolverResult<()> {
+  let res = Resolver::try_from_slice(SOURCES)?;
+
+  assert_eq!(
+    res["trailing"].to_source()?,
+    "$a ->\n  [x] x\n  *[] { $a }{\" \"}"
+  );
+  Ok(())
+}
+
+/// A nested selector with more than one branch cannot be written on the line
+/// of its branch.
+#[cfg(feature = "serde")]
+#[test]
+fn test_unprintable_nested_selector() -> anyhow::Result<()> {
+  use glossa_dsl::Error;
+
+  let res = Resolver::try_from_slice(SOURCES)?;
+  let mut json = serde_json::to_value(&res["nested"])?;
+  let cases = json["Conditional"]["cases"]
+    .as_array_mut()
+    .and_then(|cases| cases[0][1]["Conditional"]["cases"].as_array_mut())
+    .expect("nested cases");
+  cases.push(cases[0].clone());
+
+  let tmpl: glossa_dsl::Template = serde_json::from_value(json)?;
+  assert!(matches!(tmpl.to_source(), Err(Error::SourceError(_))));
+
+  // `Display` never fails; the nested selector spans multiple lines.
+  let text = tmpl.to_string();
+  assert!(text.lines().count() > 3);
+  if let glossa_dsl::Template::Conditional(selector) = &tmpl {
+    assert!(matches!(selector.to_source(), Err(Error::SourceError(_))));
+    assert_eq!(format!("{selector}"), text);
+  }
+  Ok(())
+}
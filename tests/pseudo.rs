@@ -0,0 +1,86 @@
+use glossa_dsl::{Resolver, error::ResolverResult, pseudo::PseudoOptions};
+
+mod common;
+
+fn init_resolver() -> ResolverResult<Resolver> {
+  common::init_resolver_with(&[])
+}
+
+#[test]
+fn test_pseudo_messages_still_resolve() -> ResolverResult<()> {
+  let pseudo = init_resolver()?.pseudo_localize(&PseudoOptions::default());
+
+  // Selector keys are untouched, and `{g}` is bracketed on its own.
+  assert_eq!(
+    pseudo.get_with_context("time-period", &[("period", "morning")])?,
+    "[[Ĝööđ~~] Ṁöŕñîñĝ~~~]"
+  );
+
+  let ctx = [("name", "Alice"), ("when", "evening")];
+  assert_eq!(
+    pseudo.get_with_context("greeting", &ctx)?,
+    "[[[Ĝööđ~~] evening~], Alice!~]"
+  );
+  Ok(())
+}
+
+#[test]
+fn test_pseudo_options() -> ResolverResult<()> {
+  let options = PseudoOptions {
+    accents: false,
+    expansion: 100,
+    padding: '-',
+    brackets: None,
+  };
+  let pseudo = init_resolver()?.pseudo_localize(&options);
+  assert_eq!(pseudo.try_get("g")?, "Good----");
+
+  // The pseudo-locale can be written back as source.
+  let source = pseudo["greeting"].to_source()?;
+  assert_eq!(source, "{ time-period(period: $when) }, { $name }!---");
+  let reparsed: Resolver = [("greeting", source.as_str())].try_into()?;
+  assert_eq!(reparsed["greeting"], pseudo["greeting"]);
+  Ok(())
+}
+
+#[test]
+fn test_pseudo_keeps_selector_inputs() -> ResolverResult<()> {
+  let de: Resolver = [
+    ("-brand", "Browser"),
+    ("-masculine", "masculine"),
+    ("-brand.gender", "{ -masculine }"),
+    (
+      "adj",
+      "{ -brand.gender ->\n  [masculine] neuer\n  [feminine] neue\n}",
+    ),
+    (
+      "title",
+      "{ -brand.gender ->\n  [masculine] Der { adj } { -brand }\n  *[other] Das { -brand }\n}",
+    ),
+  ]
+  .try_into()?;
+  let pseudo = de.pseudo_localize(&PseudoOptions::default());
+
+  // Without a default branch, a changed input would be an error.
+  assert_eq!(pseudo.try_get("adj")?, "[ñéûéŕ~~]");
+  assert_eq!(pseudo.try_get("title")?, "[Đéŕ [ñéûéŕ~~] [Ɓŕöŵšéŕ~~~]~~]");
+
+  // The input, and the messages it references, are kept as they are.
+  assert_eq!(pseudo["-brand.gender"], de["-brand.gender"]);
+  assert_eq!(pseudo.try_get("-masculine")?, "masculine");
+  Ok(())
+}
+
+#[test]
+fn test_pseudo_keeps_markup_and_parameters() -> ResolverResult<()> {
+  let res = Resolver::try_from_slice(&[("m", "<b>Hi</b> { $name }")])?;
+  let pseudo = res.pseudo_localize(&PseudoOptions::default());
+
+  // Only the text between the tags is accented and counted for expansion.
+  assert_eq!(pseudo["m"].to_source()?, "[<b>Ĥî</b> { $name }~]");
+  assert_eq!(
+    pseudo.get_with_context("m", &[("name", "Al")])?,
+    "[<b>Ĥî</b> Al~]"
+  );
+  Ok(())
+}
@@ -0,0 +1,49 @@
+#![cfg(feature = "reload")]
+
+use std::{
+  fs::{self, File},
+  time::{Duration, SystemTime},
+};
+
+use glossa_dsl::{
+  error::ResolverResult,
+  reload::{ReloadStatus, ReloadableResolver},
+};
+
+#[test]
+fn test_poll_reloads_valid_changes_only() -> ResolverResult<()> {
+  let dir = std::env::temp_dir().join("glossa-dsl-reload-test");
+  fs::create_dir_all(&dir)?;
+  let file = dir.join("en.toml");
+
+  // Sets an explicit mtime, because the file system may have a coarse
+  // timestamp resolution.
+  let write = |content: &str, secs: u64| -> ResolverResult<()> {
+    fs::write(&file, content)?;
+    let time = SystemTime::UNIX_EPOCH + Duration::from_secs(secs);
+    File::options()
+      .write(true)
+      .open(&file)?
+      .set_modified(time)?;
+    Ok(())
+  };
+
+  write(r#"greeting = "Hello { $name }""#, 1_000)?;
+  let mut res = ReloadableResolver::new([&file])?;
+  let ctx = [("name", "Tom")];
+
+  assert!(matches!(res.poll(), ReloadStatus::Unchanged));
+  assert_eq!(res.get_with_context("greeting", &ctx)?, "Hello Tom");
+
+  write(r#"greeting = "Hi { $name }""#, 2_000)?;
+  assert!(matches!(res.poll(), ReloadStatus::Reloaded));
+  assert_eq!(res.get_with_context("greeting", &ctx)?, "Hi Tom");
+
+  // An undefined reference fails validation; the old messages are kept.
+  write(r#"greeting = "{ hi } { $name }""#, 3_000)?;
+  assert!(matches!(res.poll(), ReloadStatus::Failed(_)));
+  assert_eq!(res.get_with_context("greeting", &ctx)?, "Hi Tom");
+  assert!(matches!(res.poll(), ReloadStatus::Unchanged));
+
+  Ok(())
+}
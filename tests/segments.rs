@@ -0,0 +1,117 @@
+use glossa_dsl::{
+  Resolver,
+  error::ResolverResult,
+  escape::HtmlEscaper,
+  resolver::{EscapeMode, ResolveOptions, Segment},
+};
+
+mod common;
+
+fn init_resolver() -> ResolverResult<Resolver> {
+  common::init_resolver_with(&[(
+    "greeting",
+    "{ time-period(period: $when) }, <b>{ $name }</b>!",
+  )])
+}
+
+fn concat(segments: &[Segment]) -> String {
+  segments
+    .iter()
+    .map(|s| match s {
+      Segment::Text(text) => text,
+      Segment::Parameter { value, .. } => value,
+      Segment::Reference { text, .. } => text,
+    })
+    .map(|s| s.as_str())
+    .collect()
+}
+
+#[test]
+fn test_nested_references_are_one_segment() -> ResolverResult<()> {
+  let res = init_resolver()?;
+  let ctx = [("name", "Alice"), ("when", "evening")];
+  let options = ResolveOptions::default();
+
+  let segments = res.resolve_segments("greeting", &ctx, &options)?;
+  assert_eq!(
+    segments,
+    [
+      Segment::Reference {
+        key: "time-period".into(),
+        text: "Good evening".into()
+      },
+      Segment::Text(", <b>".into()),
+      Segment::Parameter {
+        name: "name".into(),
+        value: "Alice".into()
+      },
+      Segment::Text("</b>!".into()),
+    ]
+  );
+  assert_eq!(
+    concat(&segments),
+    res
+      .resolve("greeting", &ctx, &options)?
+      .text
+  );
+
+  // The top-level message itself is not a reference.
+  let segments =
+    res.resolve_segments("time-period", &[("period", "morning")], &options)?;
+  assert_eq!(
+    segments,
+    [
+      Segment::Reference {
+        key: "g".into(),
+        text: "Good".into()
+      },
+      Segment::Text(" Morning".into()),
+    ]
+  );
+  Ok(())
+}
+
+#[test]
+fn test_segments_with_options() -> ResolverResult<()> {
+  let res = init_resolver()?;
+  let ctx = [("name", "Alice"), ("when", "evening")];
+  let options = ResolveOptions::for_locale("ar");
+
+  let segments = res.resolve_segments("greeting", &ctx, &options)?;
+  assert_eq!(
+    segments[2],
+    Segment::Parameter {
+      name: "name".into(),
+      value: "\u{2068}Alice\u{2069}".into()
+    }
+  );
+  assert_eq!(
+    concat(&segments),
+    res
+      .resolve("greeting", &ctx, &options)?
+      .text
+  );
+  Ok(())
+}
+
+#[test]
+fn test_segments_escape_parameters_only() -> ResolverResult<()> {
+  let res = init_resolver()?;
+  let ctx = [("name", "<i>"), ("when", "evening")];
+  let options = ResolveOptions {
+    escape: EscapeMode::Parameters(&HtmlEscaper),
+    ..Default::default()
+  };
+
+  // The markup of the message is kept; the parameter value is escaped.
+  let segments = res.resolve_segments("greeting", &ctx, &options)?;
+  assert_eq!(segments[1], Segment::Text(", <b>".into()));
+  assert_eq!(
+    segments[2],
+    Segment::Parameter {
+      name: "name".into(),
+      value: "&lt;i&gt;".into()
+    }
+  );
+  Ok(())
+}
@@ -7,6 +7,7 @@ cargo test --package glossa-dsl --test serde --features=serde -- test_ser_no_std
 */
 
 use glossa_dsl::{Resolver, error::ResolverResult};
+use tap::Pipe;
 
 #[ignore]
 #[test]
@@ -29,3 +30,28 @@ fn test_ser_no_std() -> ResolverResult<()> {
 
   Ok(())
 }
+
+/// JSON of a selector serialized by 0.0.16, which has neither an input, a
+/// default key, nor comments
+#[test]
+fn test_deserialize_old_selector() -> anyhow::Result<()> {
+  let json = r#"{
+    "Conditional": {
+      "param": "gender",
+      "cases": [["male", { "Parts": [{ "Text": "Mr." }] }]],
+      "default": { "Parts": [{ "Text": "Ms." }] }
+    }
+  }"#;
+  let tmpl: glossa_dsl::Template = serde_json::from_str(json)?;
+
+  let res: Resolver =
+    [("salutation", "$gender ->\n [male] Mr.\n *[] Ms.")].try_into()?;
+  assert_eq!(tmpl, res["salutation"]);
+
+  let res = [("old".into(), tmpl)]
+    .into_iter()
+    .collect::<glossa_dsl::resolver::AST>()
+    .pipe(Resolver::new);
+  assert_eq!(res.get_with_context("old", &[("gender", "female")])?, "Ms.");
+  Ok(())
+}
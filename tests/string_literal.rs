@@ -0,0 +1,36 @@
+use glossa_dsl::{Resolver, error::ResolverResult};
+
+#[test]
+fn test_string_literal_placeables() -> ResolverResult<()> {
+  let res: Resolver = [
+    ("braces", r#"{"{"}a{"}"} { "[" }*{"]"}"#),
+    ("nbsp", r#"100{"\u00A0"}km"#),
+    ("emoji", r#"{"\U01F431"} {"\"quoted\" \\ back"}"#),
+    ("kept", "{{ {a} }} { \"{$name}\" }"),
+    ("arg", r#"{ wrap(text: "{x}") }"#),
+    ("wrap", "[{$text}]"),
+  ]
+  .try_into()?;
+
+  assert_eq!(res.try_get("braces")?, "{a} [*]");
+  assert_eq!(res.try_get("nbsp")?, "100\u{a0}km");
+  assert_eq!(res.try_get("emoji")?, r#"🐱 "quoted" \ back"#);
+  assert_eq!(res.try_get("kept")?, "{a} {$name}");
+  assert_eq!(res.try_get("arg")?, "[{x}]");
+  Ok(())
+}
+
+#[test]
+fn test_escapes_in_call_arguments() -> ResolverResult<()> {
+  let res: Resolver = [
+    ("quote", r#"{ wrap(text: "\"") }"#),
+    ("escaped", r#"{ wrap(text: "a\\b\u00A0c") }"#),
+    ("wrap", "[{$text}]"),
+  ]
+  .try_into()?;
+
+  assert_eq!(res.try_get("quote")?, r#"["]"#);
+  assert_eq!(res.try_get("escaped")?, "[a\\b\u{a0}c]");
+  assert_eq!(res["quote"].references(), ["wrap"]);
+  Ok(())
+}
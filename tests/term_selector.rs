@@ -0,0 +1,163 @@
+use glossa_dsl::{
+  Error, Resolver,
+  error::ResolverResult,
+  escape::HtmlEscaper,
+  resolver::{BidiIsolation, EscapeMode, ResolveOptions},
+};
+
+fn init_resolver(brand: &str, gender: &str) -> ResolverResult<Resolver> {
+  [
+    ("-brand", brand),
+    ("-brand.gender", gender),
+    (
+      "new-version",
+      "{ -brand.gender ->
+        [masculine] Der neue { -brand } ist da.
+        [feminine] Die neue { -brand } ist da.
+        *[other] Das neue { -brand } ist da.
+      }",
+    ),
+    ("arrow", "Next -> page"),
+    ("menu", "Menu -> [Advanced] options"),
+  ]
+  .try_into()
+}
+
+#[test]
+fn test_select_on_attribute() -> ResolverResult<()> {
+  let res = init_resolver("Browser", "masculine")?;
+  assert_eq!(res.try_get("new-version")?, "Der neue Browser ist da.");
+
+  let res = init_resolver("App", "feminine")?;
+  assert_eq!(res.try_get("new-version")?, "Die neue App ist da.");
+
+  let res = init_resolver("Handy", "neuter")?;
+  assert_eq!(res.try_get("new-version")?, "Das neue Handy ist da.");
+
+  // Plain text that looks like a selector head stays text.
+  assert_eq!(res.try_get("arrow")?, "Next -> page");
+  assert_eq!(res.try_get("menu")?, "Menu -> [Advanced] options");
+  Ok(())
+}
+
+#[test]
+fn test_attribute_references() -> ResolverResult<()> {
+  let res = init_resolver("Browser", "masculine")?;
+  assert_eq!(res["new-version"].references(), ["-brand.gender", "-brand"]);
+  assert!(
+    res["new-version"]
+      .parameters()
+      .is_empty()
+  );
+  res.validate()?;
+
+  let source = res["new-version"].to_source()?;
+  assert!(source.starts_with("{ -brand.gender ->\n  [masculine] Der neue"));
+  assert!(source.ends_with("ist da.\n}"));
+  let reparsed: Resolver = [("new-version", source.as_str())].try_into()?;
+  assert_eq!(reparsed["new-version"], res["new-version"]);
+
+  let mut missing = res.clone();
+  missing.remove("-brand.gender");
+  assert!(matches!(
+    missing.validate(),
+    Err(Error::UndefinedReference(key, r)) if key == "new-version" && r == "-brand.gender"
+  ));
+  Ok(())
+}
+
+#[test]
+fn test_nested_attribute_selector() -> ResolverResult<()> {
+  let mut res = init_resolver("Browser", "masculine")?;
+  res.insert_source(
+    "count",
+    "$num ->
+      [one] { -brand.gender -> *[masculine] ein {\"}\"} }
+      *[other] {$num} { -brand }",
+  )?;
+  assert_eq!(res.get_with_context("count", &[("num", "one")])?, "ein }");
+
+  let source = res["count"].to_source()?;
+  assert_eq!(
+    source,
+    "$num ->\n  [one] { -brand.gender -> *[masculine] ein } }\n  *[other] { $num } { -brand }"
+  );
+  let reparsed: Resolver = [("count", source.as_str())].try_into()?;
+  assert_eq!(reparsed["count"], res["count"]);
+
+  // A selector on a single line has a single branch.
+  res.insert_source("inline", "{ -brand.gender -> *[other] Es }")?;
+  assert_eq!(res.try_get("inline")?, "Es");
+  Ok(())
+}
+
+/// Attributes whose rendered text would change with escaping or isolation
+fn init_inputs() -> ResolverResult<Resolver> {
+  [
+    ("-pair", "m&f"),
+    ("-fem", "feminine"),
+    ("-nested.gender", "{ -fem }"),
+    ("-param.gender", "{ $g }"),
+    ("pair", "{ -pair ->\n  [m&f] both\n  *[other] other\n}"),
+    (
+      "nested",
+      "{ -nested.gender ->\n  [feminine] F\n  *[other] other\n}",
+    ),
+    (
+      "param",
+      "{ -param.gender ->\n  [feminine] F\n  *[other] other\n}",
+    ),
+  ]
+  .try_into()
+}
+
+#[test]
+fn test_selector_input_is_not_escaped() -> ResolverResult<()> {
+  let res = init_inputs()?;
+  let options = ResolveOptions {
+    escape: EscapeMode::Text(&HtmlEscaper),
+    ..Default::default()
+  };
+  assert_eq!(
+    res
+      .resolve("pair", &[], &options)?
+      .text,
+    "both"
+  );
+  Ok(())
+}
+
+#[test]
+fn test_selector_input_references_are_not_isolated() -> ResolverResult<()> {
+  let res = init_inputs()?;
+  let options = ResolveOptions {
+    bidi_isolation: BidiIsolation::ParametersAndReferences,
+    ..Default::default()
+  };
+  assert_eq!(
+    res
+      .resolve("nested", &[], &options)?
+      .text,
+    "F"
+  );
+  Ok(())
+}
+
+#[test]
+fn test_selector_input_parameters_are_not_isolated() -> ResolverResult<()> {
+  let res = init_inputs()?;
+  let ctx = [("g", "feminine")];
+
+  let options = ResolveOptions::for_locale("ar");
+  assert_eq!(
+    res
+      .resolve("param", &ctx, &options)?
+      .text,
+    "F"
+  );
+
+  // The default of the resolvers of RTL locales
+  let res = res.with_bidi_isolation(BidiIsolation::for_locale("ar"));
+  assert_eq!(res.get_with_context("param", &ctx)?, "F");
+  Ok(())
+}
@@ -0,0 +1,54 @@
+use glossa_dsl::{Resolver, error::ResolverResult};
+
+#[test]
+fn test_trim_markers() -> ResolverResult<()> {
+  let res: Resolver = [
+    ("list", "(   {~ $a ~}   ,   {~$b~}   )"),
+    ("call", "<\n  {~ wrap(x: $a) ~}\n>"),
+    ("wrap", "[{$x}]"),
+    ("literal", r#"a  {~" b "~}  c"#),
+    ("keep", r#"{" "}{~ $a }"#),
+  ]
+  .try_into()?;
+
+  let ctx = [("a", "1"), ("b", "2")];
+  assert_eq!(res.get_with_context("list", &ctx)?, "(1,2)");
+  assert_eq!(res.get_with_context("call", &ctx)?, "<[1]>");
+  assert_eq!(res.try_get("literal")?, "a b c");
+  // Only the source text is trimmed, not string literals.
+  assert_eq!(res.get_with_context("keep", &ctx)?, " 1");
+  Ok(())
+}
+
+#[test]
+fn test_branch_leading_whitespace() -> ResolverResult<()> {
+  let res: Resolver = [(
+    "indent",
+    r#"$level ->
+      [1] {"  "}- {$item}
+      *[0] - {$item}"#,
+  )]
+  .try_into()?;
+
+  let text = res.get_with_context("indent", &[("item", "x"), ("level", "1")])?;
+  assert_eq!(text, "  - x");
+  Ok(())
+}
+
+#[test]
+fn test_branch_content_on_next_line() -> ResolverResult<()> {
+  let res: Resolver = [(
+    "unread",
+    "$num ->
+      [0]
+        No messages
+      *[other] {$num} messages",
+  )]
+  .try_into()?;
+
+  assert_eq!(
+    res.get_with_context("unread", &[("num", "0")])?,
+    "No messages"
+  );
+  Ok(())
+}